@@ -2,8 +2,8 @@
 
 use fetchttp::*;
 use iai_callgrind::{
-    library_benchmark, library_benchmark_group, main, EventKind, FlamegraphConfig,
-    LibraryBenchmarkConfig, RegressionConfig, Tool, ValgrindTool,
+    library_benchmark, library_benchmark_group, main, Bbv, Callgrind, Dhat, Drd, EventKind,
+    FlamegraphConfig, Helgrind, LibraryBenchmarkConfig, Massif, Memcheck,
 };
 
 #[library_benchmark]
@@ -380,16 +380,16 @@ library_benchmark_group!(
 
 main!(
     config = LibraryBenchmarkConfig::default()
-                .tool(Tool::new(ValgrindTool::DHAT))
-                .tool(Tool::new(ValgrindTool::Massif))
-                .tool(Tool::new(ValgrindTool::BBV))
-                .tool(Tool::new(ValgrindTool::Memcheck))
-                .tool(Tool::new(ValgrindTool::Helgrind))
-                .tool(Tool::new(ValgrindTool::DRD))
-                .flamegraph(FlamegraphConfig::default())
-                .regression(
-                    RegressionConfig::default()
-                    .limits([(EventKind::Ir, 5.0)])
+                .tool(Dhat::default())
+                .tool(Massif::default())
+                .tool(Bbv::default())
+                .tool(Memcheck::default())
+                .tool(Helgrind::default())
+                .tool(Drd::default())
+                .tool(
+                    Callgrind::default()
+                    .flamegraph(FlamegraphConfig::default())
+                    .soft_limits([(EventKind::Ir, 5.0)])
                     );
     library_benchmark_groups =
         headers_bench,
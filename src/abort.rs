@@ -8,13 +8,13 @@
 //!
 //! ## Basic Abort Controller
 //!
-//! ```rust
+//! ```rust,no_run
 //! use fetchttp::*;
 //! use std::time::Duration;
 //! use tokio::time::sleep;
 //!
 //! #[tokio::main]
-//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
 //!     let controller = AbortController::new();
 //!     let signal = controller.signal().clone();
 //!     
@@ -49,6 +49,8 @@
 //! ```
 
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::Notify;
 
 /// A signal that can be used to cancel operations.
 ///
@@ -56,9 +58,10 @@ use std::sync::{Arc, Mutex};
 /// be checked to see if an operation should be cancelled and can provide a
 /// reason for the cancellation.
 ///
-/// Signals can be created in two ways:
+/// Signals can be created in three ways:
 /// 1. Through an [`AbortController`] for dynamic cancellation
 /// 2. Using [`AbortSignal::abort()`] for pre-cancelled signals
+/// 3. Using [`AbortSignal::timeout()`] for signals that abort after a delay
 ///
 /// # Thread Safety
 ///
@@ -87,6 +90,8 @@ use std::sync::{Arc, Mutex};
 pub struct AbortSignal {
     /// Shared state between signal clones
     inner: Arc<Mutex<AbortSignalInner>>,
+    /// Wakes tasks waiting for the signal to be aborted
+    notify: Arc<Notify>,
 }
 
 /// Internal state of an abort signal.
@@ -124,6 +129,7 @@ impl AbortSignal {
                 aborted: false,
                 reason: None,
             })),
+            notify: Arc::new(Notify::new()),
         }
     }
 
@@ -155,9 +161,45 @@ impl AbortSignal {
                 aborted: true,
                 reason,
             })),
+            notify: Arc::new(Notify::new()),
         }
     }
 
+    /// Create an abort signal that aborts itself after `duration`.
+    ///
+    /// This mirrors the web platform's `AbortSignal.timeout()`. A Tokio task
+    /// aborts the signal with the reason `"TimeoutError"` once `duration` has
+    /// elapsed, so no [`AbortController`] is needed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called outside of a Tokio runtime.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::AbortSignal;
+    /// use std::time::Duration;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let signal = AbortSignal::timeout(Duration::from_millis(10));
+    /// assert!(!signal.aborted());
+    ///
+    /// tokio::time::sleep(Duration::from_millis(50)).await;
+    /// assert!(signal.aborted());
+    /// assert_eq!(signal.reason().unwrap(), "TimeoutError");
+    /// # });
+    /// ```
+    pub fn timeout(duration: Duration) -> Self {
+        let signal = Self::new();
+        let timer = signal.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(duration).await;
+            timer.do_abort(Some("TimeoutError".to_string()));
+        });
+        signal
+    }
+
     /// Check if the signal has been aborted.
     ///
     /// This method is typically called by operations that support cancellation
@@ -222,6 +264,25 @@ impl AbortSignal {
         if !inner.aborted {
             inner.aborted = true;
             inner.reason = reason;
+            self.notify.notify_waiters();
+        }
+    }
+
+    /// Wait until the signal is aborted (internal use).
+    ///
+    /// Completes immediately if the signal has already been aborted. Used to
+    /// cancel requests that are in flight.
+    pub(crate) async fn wait_aborted(&self) {
+        loop {
+            let notified = self.notify.notified();
+            tokio::pin!(notified);
+            // Register for the wake-up before checking, so an abort between
+            // the check and the await is not missed
+            notified.as_mut().enable();
+            if self.aborted() {
+                return;
+            }
+            notified.await;
         }
     }
 }
@@ -475,4 +536,33 @@ mod tests {
         assert_eq!(signal2.reason().unwrap(), "AbortError");
         assert_eq!(signal3.reason().unwrap(), "AbortError");
     }
+
+    #[tokio::test]
+    async fn test_abort_signal_timeout() {
+        let signal = AbortSignal::timeout(Duration::from_millis(20));
+        assert!(!signal.aborted());
+        assert!(signal.reason().is_none());
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert!(signal.aborted());
+        assert_eq!(signal.reason().unwrap(), "TimeoutError");
+    }
+
+    #[tokio::test]
+    async fn test_wait_aborted() {
+        let signal = AbortSignal::abort(None);
+        signal.wait_aborted().await;
+
+        let controller = AbortController::new();
+        let signal = controller.signal().clone();
+        let waiter = tokio::spawn(async move { signal.wait_aborted().await });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!waiter.is_finished());
+
+        controller.abort();
+        tokio::time::timeout(Duration::from_secs(1), waiter)
+            .await
+            .expect("waiter should wake up on abort")
+            .unwrap();
+    }
 }
@@ -10,7 +10,7 @@
 //! - **Empty**: No body content
 //! - **Text**: UTF-8 text content  
 //! - **Bytes**: Raw binary data
-//! - **JSON**: Structured data serialized as JSON
+//! - **JSON**: Structured data serialized as JSON (compact or pretty-printed)
 //!
 //! # Usage Examples
 //!
@@ -33,7 +33,9 @@
 
 use crate::error::{FetchError, Result, TypeError};
 use bytes::Bytes;
-use serde_json::Value;
+use futures_util::Stream;
+use serde_json::{Map, Value};
+use std::marker::PhantomData;
 
 /// Internal representation of body data sources.
 ///
@@ -50,6 +52,8 @@ pub enum BodySource {
     Bytes(Bytes),
     /// Structured JSON data
     Json(Value),
+    /// Structured JSON data serialized with indentation
+    PrettyJson(Value),
 }
 
 /// A readable stream representing request or response body data.
@@ -154,7 +158,7 @@ impl ReadableStream {
     /// let stream = ReadableStream::from_bytes(data);
     /// # tokio_test::block_on(async {
     /// let bytes = stream.array_buffer().await.unwrap();
-    /// assert_eq!(bytes, b"binary data");
+    /// assert_eq!(bytes, &b"binary data"[..]);
     /// # });
     /// ```
     pub fn from_bytes(bytes: Bytes) -> Self {
@@ -193,6 +197,62 @@ impl ReadableStream {
         }
     }
 
+    /// Create a readable stream from a JSON object map.
+    ///
+    /// Equivalent to wrapping the map in [`Value::Object`] and calling
+    /// [`from_json()`]; the content type is `application/json`.
+    ///
+    /// [`Value::Object`]: serde_json::Value::Object
+    /// [`from_json()`]: ReadableStream::from_json
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{JsonMap, JsonValue, ReadableStream};
+    ///
+    /// let mut map = JsonMap::new();
+    /// map.insert("name".to_string(), JsonValue::from("Alice"));
+    ///
+    /// let stream = ReadableStream::from_json_map(&map);
+    /// # tokio_test::block_on(async {
+    /// let parsed: JsonValue = stream.json().await.unwrap();
+    /// assert_eq!(parsed["name"], "Alice");
+    /// # });
+    /// ```
+    pub fn from_json_map(map: &Map<String, Value>) -> Self {
+        Self {
+            source: BodySource::Json(Value::Object(map.clone())),
+            used: false,
+        }
+    }
+
+    /// Create a readable stream from JSON data serialized with indentation.
+    ///
+    /// Identical to [`from_json()`] except that the JSON is pretty-printed when
+    /// the stream is converted to bytes or text, which keeps logged webhook
+    /// payloads readable. The content type is still `application/json`.
+    ///
+    /// [`from_json()`]: ReadableStream::from_json
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::ReadableStream;
+    /// use serde_json::json;
+    ///
+    /// let stream = ReadableStream::from_json_pretty(&json!({"name": "Alice"}));
+    /// # tokio_test::block_on(async {
+    /// let text = stream.text().await.unwrap();
+    /// assert_eq!(text, "{\n  \"name\": \"Alice\"\n}");
+    /// # });
+    /// ```
+    pub fn from_json_pretty(value: &Value) -> Self {
+        Self {
+            source: BodySource::PrettyJson(value.clone()),
+            used: false,
+        }
+    }
+
     /// Check if the stream is locked.
     ///
     /// In this implementation, streams are never locked as we don't support
@@ -236,7 +296,7 @@ impl ReadableStream {
     /// # tokio_test::block_on(async {
     /// let stream = ReadableStream::from_text("Hello");
     /// let bytes = stream.array_buffer().await.unwrap();
-    /// assert_eq!(bytes, b"Hello");
+    /// assert_eq!(bytes, &b"Hello"[..]);
     /// # });
     /// ```
     pub async fn array_buffer(mut self) -> Result<Bytes> {
@@ -253,6 +313,10 @@ impl ReadableStream {
                 let vec = serde_json::to_vec(&value)?;
                 Ok(Bytes::from(vec))
             }
+            BodySource::PrettyJson(value) => {
+                let vec = serde_json::to_vec_pretty(&value)?;
+                Ok(Bytes::from(vec))
+            }
         }
     }
 
@@ -272,7 +336,7 @@ impl ReadableStream {
     /// # tokio_test::block_on(async {
     /// let stream = ReadableStream::from_bytes(b"data".to_vec().into());
     /// let blob = stream.blob().await.unwrap();
-    /// assert_eq!(blob, b"data");
+    /// assert_eq!(blob, &b"data"[..]);
     /// # });
     /// ```
     pub async fn blob(self) -> Result<Bytes> {
@@ -354,10 +418,131 @@ impl ReadableStream {
             ))),
             BodySource::Text(text) => Ok(serde_json::from_str(&text)?),
             BodySource::Bytes(bytes) => Ok(serde_json::from_slice(&bytes)?),
-            BodySource::Json(value) => Ok(serde_json::from_value(value)?),
+            BodySource::Json(value) | BodySource::PrettyJson(value) => {
+                Ok(serde_json::from_value(value)?)
+            }
         }
     }
 
+    /// Consume the stream and parse only the first JSON value in it.
+    ///
+    /// Unlike [`json()`], any bytes following the first complete value are
+    /// ignored. This suits sources that append a newline and unrelated data
+    /// after the payload, or that concatenate several JSON documents.
+    ///
+    /// [`json()`]: ReadableStream::json
+    ///
+    /// # Errors
+    ///
+    /// * [`TypeError`] - If the stream was already used, is empty, or does not
+    ///   start with a valid JSON value
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::ReadableStream;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let stream = ReadableStream::from_text("{\"id\": 1}\n<trailing junk>");
+    /// let value: serde_json::Value = stream.json_first().await.unwrap();
+    /// assert_eq!(value["id"], 1);
+    /// # });
+    /// ```
+    pub async fn json_first<T: serde::de::DeserializeOwned>(self) -> Result<T> {
+        let bytes = self.array_buffer().await?;
+        serde_json::Deserializer::from_slice(&bytes)
+            .into_iter::<T>()
+            .next()
+            .ok_or_else(|| FetchError::Type(TypeError::new("Unexpected end of JSON input")))?
+            .map_err(FetchError::from)
+    }
+
+    /// Consume the stream and parse it as JSON nested at most `max_depth` deep.
+    ///
+    /// Each array or object opens one level, so `[[1]]` is nested two deep
+    /// and a bare scalar zero. The body is scanned before it is deserialized,
+    /// so a deeply nested payload is rejected without recursing into it and
+    /// cannot overflow the stack. Independently of `max_depth`, `serde_json`
+    /// refuses input nested more than 128 levels deep.
+    ///
+    /// # Errors
+    ///
+    /// * [`TypeError`] - If the stream was already used, the JSON is nested
+    ///   deeper than `max_depth`, or it is not valid JSON for `T`
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::ReadableStream;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let stream = ReadableStream::from_text("{\"a\": [1, 2]}");
+    /// let value: serde_json::Value = stream.json_guarded(2).await.unwrap();
+    /// assert_eq!(value["a"][1], 2);
+    ///
+    /// let stream = ReadableStream::from_text("[[[0]]]");
+    /// assert!(stream.json_guarded::<serde_json::Value>(2).await.is_err());
+    /// # });
+    /// ```
+    pub async fn json_guarded<T: serde::de::DeserializeOwned>(self, max_depth: usize) -> Result<T> {
+        let bytes = self.array_buffer().await?;
+        if bytes.is_empty() {
+            return Err(FetchError::Type(TypeError::new(
+                "Unexpected end of JSON input",
+            )));
+        }
+        if json_depth_exceeds(&bytes, max_depth) {
+            return Err(FetchError::Type(TypeError::new(&format!(
+                "JSON nested deeper than {} levels",
+                max_depth
+            ))));
+        }
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Consume the stream and decode a top-level JSON array one element at a time.
+    ///
+    /// Each item of the returned stream is the next array element, parsed only
+    /// when it is polled, so elements can be processed and dropped one by one
+    /// instead of materializing the whole array as a `Vec<T>`. Nested values
+    /// and strings containing brackets or commas are handled by the JSON
+    /// parser. The raw body bytes themselves are still held in memory.
+    ///
+    /// If the stream was already used or the body is not a JSON array, the
+    /// first item is an error and the stream ends. A malformed element yields
+    /// an error and ends the stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::ReadableStream;
+    /// use futures::StreamExt;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let stream = ReadableStream::from_text("[1, 2, 3]");
+    /// let numbers: Vec<u32> = stream
+    ///     .json_array_stream::<u32>()
+    ///     .map(|item| item.unwrap())
+    ///     .collect()
+    ///     .await;
+    /// assert_eq!(numbers, vec![1, 2, 3]);
+    /// # });
+    /// ```
+    pub fn json_array_stream<T: serde::de::DeserializeOwned>(
+        mut self,
+    ) -> impl Stream<Item = Result<T>> {
+        let elements = if self.used {
+            JsonArrayElements::failed(FetchError::Type(TypeError::new("Body already used")))
+        } else {
+            self.used = true;
+            match self.buffered_bytes() {
+                Ok(bytes) => JsonArrayElements::new(bytes),
+                Err(e) => JsonArrayElements::failed(e),
+            }
+        };
+        futures_util::stream::iter(elements)
+    }
+
     /// Consume the stream and return the content as text.
     ///
     /// This method consumes the entire stream and returns the content as a
@@ -395,6 +580,7 @@ impl ReadableStream {
             BodySource::Bytes(bytes) => String::from_utf8(bytes.to_vec())
                 .map_err(|_| FetchError::Type(TypeError::new("Invalid UTF-8"))),
             BodySource::Json(value) => Ok(serde_json::to_string(&value)?),
+            BodySource::PrettyJson(value) => Ok(serde_json::to_string_pretty(&value)?),
         }
     }
 
@@ -411,7 +597,7 @@ impl ReadableStream {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use fetchttp::ReadableStream;
     /// use serde_json::json;
     ///
@@ -429,7 +615,7 @@ impl ReadableStream {
             BodySource::Empty => None,
             BodySource::Text(_) => Some("text/plain;charset=UTF-8"),
             BodySource::Bytes(_) => None,
-            BodySource::Json(_) => Some("application/json"),
+            BodySource::Json(_) | BodySource::PrettyJson(_) => Some("application/json"),
         }
     }
 
@@ -439,6 +625,15 @@ impl ReadableStream {
     /// body content for HTTP transmission. Unlike `array_buffer()`, this
     /// method doesn't mark the stream as used.
     pub(crate) async fn to_bytes(&self) -> Result<Bytes> {
+        self.buffered_bytes()
+    }
+
+    /// Get the byte representation of the body content synchronously.
+    ///
+    /// All body sources are held in memory, so this never blocks. It backs
+    /// [`to_bytes()`](ReadableStream::to_bytes) and is used wherever the body
+    /// needs to be inspected outside of an async context.
+    pub(crate) fn buffered_bytes(&self) -> Result<Bytes> {
         match &self.source {
             BodySource::Empty => Ok(Bytes::new()),
             BodySource::Text(text) => Ok(Bytes::from(text.as_bytes().to_vec())),
@@ -447,6 +642,36 @@ impl ReadableStream {
                 let vec = serde_json::to_vec(value)?;
                 Ok(Bytes::from(vec))
             }
+            BodySource::PrettyJson(value) => {
+                let vec = serde_json::to_vec_pretty(value)?;
+                Ok(Bytes::from(vec))
+            }
+        }
+    }
+
+    /// Check whether the body holds no content.
+    ///
+    /// JSON values always serialize to at least one byte, so only empty,
+    /// zero-length text and zero-length byte sources are empty.
+    pub(crate) fn is_empty(&self) -> bool {
+        match &self.source {
+            BodySource::Empty => true,
+            BodySource::Text(text) => text.is_empty(),
+            BodySource::Bytes(bytes) => bytes.is_empty(),
+            BodySource::Json(_) | BodySource::PrettyJson(_) => false,
+        }
+    }
+
+    /// Estimate the bytes of heap memory held by the body content.
+    ///
+    /// Byte bodies count their full length even when the buffer is shared
+    /// with other handles. JSON values are estimated from their structure.
+    pub(crate) fn approx_heap_size(&self) -> usize {
+        match &self.source {
+            BodySource::Empty => 0,
+            BodySource::Text(text) => text.capacity(),
+            BodySource::Bytes(bytes) => bytes.len(),
+            BodySource::Json(value) | BodySource::PrettyJson(value) => json_heap_size(value),
         }
     }
 
@@ -457,7 +682,7 @@ impl ReadableStream {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use fetchttp::ReadableStream;
     ///
     /// # tokio_test::block_on(async {
@@ -473,6 +698,57 @@ impl ReadableStream {
     }
 }
 
+/// Whether the arrays and objects in the JSON text `bytes` nest deeper than
+/// `max_depth`.
+///
+/// Brackets inside strings are skipped; the text is not otherwise validated.
+fn json_depth_exceeds(bytes: &[u8], max_depth: usize) -> bool {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for &byte in bytes {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match byte {
+            b'"' => in_string = true,
+            b'[' | b'{' => {
+                depth += 1;
+                if depth > max_depth {
+                    return true;
+                }
+            }
+            b']' | b'}' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Estimate the bytes of heap memory held by a JSON value.
+fn json_heap_size(value: &Value) -> usize {
+    match value {
+        Value::Null | Value::Bool(_) | Value::Number(_) => 0,
+        Value::String(text) => text.capacity(),
+        Value::Array(items) => {
+            items.capacity() * std::mem::size_of::<Value>()
+                + items.iter().map(json_heap_size).sum::<usize>()
+        }
+        Value::Object(map) => map
+            .iter()
+            .map(|(key, value)| {
+                key.capacity() + std::mem::size_of::<(String, Value)>() + json_heap_size(value)
+            })
+            .sum(),
+    }
+}
+
 // Convenient conversion implementations
 impl From<&str> for ReadableStream {
     fn from(text: &str) -> Self {
@@ -504,6 +780,110 @@ impl From<Value> for ReadableStream {
     }
 }
 
+/// Iterator over the elements of a JSON array held in a byte buffer.
+pub(crate) struct JsonArrayElements<T> {
+    /// The complete array source
+    bytes: Bytes,
+    /// Offset of the next unread byte
+    pos: usize,
+    /// Whether the opening `[` has been consumed
+    started: bool,
+    /// Error to yield before anything else
+    error: Option<FetchError>,
+    /// Set once the array end or an error has been reached
+    done: bool,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> JsonArrayElements<T> {
+    /// Iterate over the array contained in `bytes`.
+    pub(crate) fn new(bytes: Bytes) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            started: false,
+            error: None,
+            done: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// An iterator that yields only `error`.
+    pub(crate) fn failed(error: FetchError) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::new(Bytes::new())
+        }
+    }
+
+    /// Advance past whitespace and return the next byte, if any.
+    fn peek_non_whitespace(&mut self) -> Option<u8> {
+        while let Some(&b) = self.bytes.get(self.pos) {
+            if !b.is_ascii_whitespace() {
+                return Some(b);
+            }
+            self.pos += 1;
+        }
+        None
+    }
+
+    /// Stop iterating and produce `message` as a parse error.
+    fn fail<U>(&mut self, message: &str) -> Option<Result<U>> {
+        self.done = true;
+        Some(Err(FetchError::Type(TypeError::new(message))))
+    }
+}
+
+impl<T: serde::de::DeserializeOwned> Iterator for JsonArrayElements<T> {
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.error.take() {
+            self.done = true;
+            return Some(Err(error));
+        }
+        if self.done {
+            return None;
+        }
+
+        if !self.started {
+            if self.peek_non_whitespace() != Some(b'[') {
+                return self.fail("Expected a JSON array");
+            }
+            self.pos += 1;
+            self.started = true;
+            if self.peek_non_whitespace() == Some(b']') {
+                self.done = true;
+                return None;
+            }
+        }
+
+        if self.peek_non_whitespace().is_none() {
+            return self.fail("Unexpected end of JSON input");
+        }
+
+        // Parse exactly one value; the parser stops at the `,` or `]` after it
+        let mut values =
+            serde_json::Deserializer::from_slice(&self.bytes[self.pos..]).into_iter::<T>();
+        let element = match values.next() {
+            Some(Ok(element)) => element,
+            Some(Err(e)) => {
+                self.done = true;
+                return Some(Err(e.into()));
+            }
+            None => return self.fail("Unexpected end of JSON input"),
+        };
+        self.pos += values.byte_offset();
+
+        match self.peek_non_whitespace() {
+            Some(b',') => self.pos += 1,
+            Some(b']') => self.done = true,
+            _ => return self.fail("Expected ',' or ']' in JSON array"),
+        }
+        Some(Ok(element))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -532,6 +912,35 @@ mod tests {
         assert_eq!(parsed["number"], 42);
     }
 
+    #[tokio::test]
+    async fn test_readable_stream_json_map() {
+        let mut map = Map::new();
+        map.insert("key".to_string(), Value::from("value"));
+        map.insert("list".to_string(), serde_json::json!([1, 2, 3]));
+
+        let stream = ReadableStream::from_json_map(&map);
+        assert_eq!(stream.get_content_type(), Some("application/json"));
+
+        let parsed: Map<String, Value> = stream.json().await.unwrap();
+        assert_eq!(parsed, map);
+    }
+
+    #[tokio::test]
+    async fn test_readable_stream_json_pretty() {
+        let value = serde_json::json!({"key": "value", "nested": {"number": 42}});
+        let stream = ReadableStream::from_json_pretty(&value);
+        assert_eq!(stream.get_content_type(), Some("application/json"));
+
+        let bytes = stream.to_bytes().await.unwrap();
+        let text = String::from_utf8(bytes.to_vec()).unwrap();
+        assert!(text.contains('\n'));
+        assert!(text.contains("\n  \"key\": \"value\""));
+        assert!(text.contains("\n    \"number\": 42"));
+
+        let parsed: serde_json::Value = stream.json().await.unwrap();
+        assert_eq!(parsed, value);
+    }
+
     #[tokio::test]
     async fn test_readable_stream_empty() {
         let stream = ReadableStream::empty();
@@ -610,6 +1019,38 @@ mod tests {
         assert!(matches!(result.unwrap_err(), FetchError::Type(_)));
     }
 
+    #[tokio::test]
+    async fn test_json_first_ignores_trailing_data() {
+        let stream = ReadableStream::from_text("{\"key\": \"value\"}\n\x00garbage{");
+        let parsed: serde_json::Value = stream.json_first().await.unwrap();
+        assert_eq!(parsed["key"], "value");
+
+        let stream = ReadableStream::from_text("   ");
+        let result: Result<serde_json::Value> = stream.json_first().await;
+        assert!(matches!(result, Err(FetchError::Type(_))));
+    }
+
+    #[tokio::test]
+    async fn test_json_guarded() {
+        let stream = ReadableStream::from_text(r#"{"a": [{"b": "[[[{{{"}]}"#);
+        let parsed: serde_json::Value = stream.json_guarded(3).await.unwrap();
+        assert_eq!(parsed["a"][0]["b"], "[[[{{{");
+
+        let stream = ReadableStream::from_text(r#"{"a": [{"b": "\"[["}]}"#);
+        assert!(stream.json_guarded::<serde_json::Value>(3).await.is_ok());
+
+        let stream = ReadableStream::from_text(r#"{"a": [{"b": [1]}]}"#);
+        let result: Result<serde_json::Value> = stream.json_guarded(3).await;
+        assert!(
+            matches!(result, Err(FetchError::Type(e)) if e.message().contains("deeper than 3"))
+        );
+
+        let stream = ReadableStream::from_text("42");
+        assert_eq!(stream.json_guarded::<u32>(0).await.unwrap(), 42);
+        let stream = ReadableStream::empty();
+        assert!(stream.json_guarded::<serde_json::Value>(8).await.is_err());
+    }
+
     #[tokio::test]
     async fn test_json_empty_body_error() {
         let stream = ReadableStream::empty();
@@ -628,4 +1069,52 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), FetchError::Type(_)));
     }
+
+    #[tokio::test]
+    async fn test_json_array_stream_tricky_elements() {
+        use futures::StreamExt;
+
+        let source = r#" [ {"a": [1, {"b": "]"}]}, "x,y]", [[]], null ] "#;
+        let items: Vec<Value> = ReadableStream::from_text(source)
+            .json_array_stream::<Value>()
+            .map(|item| item.unwrap())
+            .collect()
+            .await;
+        assert_eq!(
+            items,
+            vec![
+                serde_json::json!({"a": [1, {"b": "]"}]}),
+                serde_json::json!("x,y]"),
+                serde_json::json!([[]]),
+                Value::Null,
+            ]
+        );
+
+        let empty: Vec<Result<Value>> = ReadableStream::from_text("[]")
+            .json_array_stream()
+            .collect()
+            .await;
+        assert!(empty.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_json_array_stream_errors() {
+        use futures::StreamExt;
+
+        let items: Vec<Result<Value>> = ReadableStream::from_text("{\"a\": 1}")
+            .json_array_stream()
+            .collect()
+            .await;
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+
+        let items: Vec<Result<u32>> = ReadableStream::from_text("[1, 2")
+            .json_array_stream()
+            .collect()
+            .await;
+        // The trailing element is not terminated, so it is reported as an error
+        assert_eq!(items.len(), 2);
+        assert_eq!(*items[0].as_ref().unwrap(), 1);
+        assert!(items[1].is_err());
+    }
 }
@@ -0,0 +1,413 @@
+//! Decoding of compressed response bodies.
+//!
+//! Enabled by the `compression` feature. Response bodies whose
+//! `Content-Encoding` names a supported coding are decoded before the
+//! [`Response`](crate::Response) is built, and the headers describing the
+//! encoded form are removed so they match the body the caller sees.
+
+use crate::error::{FetchError, NetworkError, Result};
+use bytes::Bytes;
+use std::io::Read;
+
+/// The two bytes every gzip member starts with.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Decode `body` according to the `Transfer-Encoding` and `Content-Encoding` in `headers`.
+///
+/// Both headers list codings in the order they were applied, so they are
+/// undone in reverse: `Content-Encoding: gzip, br` is brotli-decoded first,
+/// then gunzipped. Compression codings in `Transfer-Encoding`, which some
+/// noncompliant servers send instead of `Content-Encoding`, were applied last
+/// and are undone first; `chunked` has already been handled by hyper. Decoded
+/// codings are removed from the headers, along with `Content-Length`, since
+/// it describes the encoded body.
+///
+/// A coding this module does not understand is a [`NetworkError`], unless
+/// `lenient` is set, in which case the body and that header are left
+/// untouched. When a gzip layer fails to decode under `lenient` and does not
+/// start with the gzip magic bytes, it is assumed to be mislabeled plaintext:
+/// a warning is logged and the bytes decoded so far are returned. Any other
+/// decoding failure is a [`NetworkError`].
+///
+/// With a `limit`, decoding stops as soon as any layer grows past `limit`
+/// bytes and the client's body limit error is returned, so a small
+/// compressed body cannot expand into an unbounded one.
+pub(crate) fn decode_response_body(
+    headers: &mut http::HeaderMap,
+    body: Bytes,
+    lenient: bool,
+    limit: Option<usize>,
+) -> Result<Bytes> {
+    let body = decode_transfer_codings(headers, body, lenient, limit)?;
+    decode_content_codings(headers, body, lenient, limit)
+}
+
+/// Undo the compression codings listed in `Transfer-Encoding`.
+///
+/// On success only `chunked`, if it was listed, is kept in the header.
+fn decode_transfer_codings(
+    headers: &mut http::HeaderMap,
+    body: Bytes,
+    lenient: bool,
+    limit: Option<usize>,
+) -> Result<Bytes> {
+    let listed = parse_codings(headers, &http::header::TRANSFER_ENCODING);
+    let chunked = listed.iter().any(|coding| coding == "chunked");
+    let codings: Vec<String> = listed
+        .into_iter()
+        .filter(|coding| coding != "chunked")
+        .collect();
+    if codings.is_empty() || !check_supported("Transfer-Encoding", &codings, lenient)? {
+        return Ok(body);
+    }
+
+    let decoded = decode_codings("Transfer-Encoding", &codings, body, lenient, limit)?;
+    if chunked {
+        headers.insert(
+            http::header::TRANSFER_ENCODING,
+            http::HeaderValue::from_static("chunked"),
+        );
+    } else {
+        headers.remove(http::header::TRANSFER_ENCODING);
+    }
+    headers.remove(http::header::CONTENT_LENGTH);
+    Ok(decoded)
+}
+
+/// Undo the codings listed in `Content-Encoding`.
+fn decode_content_codings(
+    headers: &mut http::HeaderMap,
+    body: Bytes,
+    lenient: bool,
+    limit: Option<usize>,
+) -> Result<Bytes> {
+    let codings = parse_codings(headers, &http::header::CONTENT_ENCODING);
+    if !headers.contains_key(http::header::CONTENT_ENCODING)
+        || !check_supported("Content-Encoding", &codings, lenient)?
+    {
+        return Ok(body);
+    }
+
+    let decoded = decode_codings("Content-Encoding", &codings, body, lenient, limit)?;
+    headers.remove(http::header::CONTENT_ENCODING);
+    headers.remove(http::header::CONTENT_LENGTH);
+    Ok(decoded)
+}
+
+/// Parse the coding list in header `name`, lowercased and without `identity`.
+fn parse_codings(headers: &http::HeaderMap, name: &http::HeaderName) -> Vec<String> {
+    headers
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|coding| coding.trim().to_ascii_lowercase())
+        .filter(|coding| !coding.is_empty() && coding != "identity")
+        .collect()
+}
+
+/// Check that every coding can be decoded before any of them is.
+///
+/// Returns `Ok(false)` when an unknown coding should leave the body undecoded,
+/// so an unknown coding never leaves a half-decoded body.
+fn check_supported(header: &str, codings: &[String], lenient: bool) -> Result<bool> {
+    match codings.iter().find(|coding| !is_supported(coding)) {
+        None => Ok(true),
+        Some(unknown) if lenient => {
+            log::warn!(
+                "response uses unsupported {}: {}; returning the body undecoded",
+                header,
+                unknown
+            );
+            Ok(false)
+        }
+        Some(unknown) => Err(FetchError::Network(NetworkError::new(&format!(
+            "Unsupported content coding: {}",
+            unknown
+        )))),
+    }
+}
+
+/// Undo `codings`, last applied first.
+fn decode_codings(
+    header: &str,
+    codings: &[String],
+    body: Bytes,
+    lenient: bool,
+    limit: Option<usize>,
+) -> Result<Bytes> {
+    let mut decoded = body;
+    for coding in codings.iter().rev() {
+        match decode_layer(coding, &decoded, limit) {
+            Ok(layer) => {
+                if let Some(limit) = limit.filter(|&limit| layer.len() > limit) {
+                    return Err(crate::client::body_limit_error(limit));
+                }
+                decoded = layer;
+            }
+            Err(e) if lenient && is_gzip(coding) && !decoded.starts_with(&GZIP_MAGIC) => {
+                log::warn!(
+                    "response labeled {}: {} is not gzip data ({}); \
+                     returning the body undecoded",
+                    header,
+                    coding,
+                    e
+                );
+                break;
+            }
+            Err(e) => {
+                return Err(FetchError::Network(NetworkError::new(&format!(
+                    "Failed to decode {} response body: {}",
+                    coding, e
+                ))))
+            }
+        }
+    }
+    Ok(decoded)
+}
+
+/// Whether `coding` names a gzip coding.
+fn is_gzip(coding: &str) -> bool {
+    matches!(coding, "gzip" | "x-gzip")
+}
+
+/// Whether this module can decode `coding`.
+fn is_supported(coding: &str) -> bool {
+    is_gzip(coding) || matches!(coding, "br" | "deflate")
+}
+
+/// Undo a single supported coding.
+///
+/// With a `limit`, at most `limit + 1` bytes are decoded, which is enough for
+/// the caller to tell that the limit was passed.
+fn decode_layer(coding: &str, data: &[u8], limit: Option<usize>) -> std::io::Result<Bytes> {
+    match coding {
+        "br" => unbrotli(data, limit),
+        "deflate" => inflate(data, limit),
+        _ => gunzip(data, limit),
+    }
+}
+
+/// Read `decoder` to the end, stopping after `limit + 1` bytes.
+fn read_limited(decoder: impl Read, limit: Option<usize>) -> std::io::Result<Vec<u8>> {
+    let cap = limit.map_or(u64::MAX, |limit| limit as u64 + 1);
+    let mut decoded = Vec::new();
+    decoder.take(cap).read_to_end(&mut decoded)?;
+    Ok(decoded)
+}
+
+/// Decompress gzip data, including multi-member streams.
+fn gunzip(data: &[u8], limit: Option<usize>) -> std::io::Result<Bytes> {
+    read_limited(flate2::read::MultiGzDecoder::new(data), limit).map(Bytes::from)
+}
+
+/// Decompress `deflate` data.
+///
+/// The coding is zlib-wrapped deflate, but many servers send raw deflate
+/// streams instead; like browsers, those are accepted too.
+fn inflate(data: &[u8], limit: Option<usize>) -> std::io::Result<Bytes> {
+    match read_limited(flate2::read::ZlibDecoder::new(data), limit) {
+        Ok(decoded) => Ok(Bytes::from(decoded)),
+        Err(e) => read_limited(flate2::read::DeflateDecoder::new(data), limit)
+            .map(Bytes::from)
+            .map_err(|_| e),
+    }
+}
+
+/// Decompress brotli data.
+fn unbrotli(data: &[u8], limit: Option<usize>) -> std::io::Result<Bytes> {
+    read_limited(brotli::Decompressor::new(data, 4096), limit).map(Bytes::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn gzip(data: &[u8]) -> Bytes {
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(data).unwrap();
+        Bytes::from(encoder.finish().unwrap())
+    }
+
+    fn brotli(data: &[u8]) -> Bytes {
+        let mut encoded = Vec::new();
+        {
+            let mut encoder = brotli::CompressorWriter::new(&mut encoded, 4096, 5, 22);
+            encoder.write_all(data).unwrap();
+        }
+        Bytes::from(encoded)
+    }
+
+    fn headers_with_encoding(coding: &str) -> http::HeaderMap {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(http::header::CONTENT_ENCODING, coding.parse().unwrap());
+        headers.insert(http::header::CONTENT_LENGTH, "10".parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_decode_gzip() {
+        let mut headers = headers_with_encoding("gzip");
+        let body = decode_response_body(&mut headers, gzip(b"hello gzip"), false, None).unwrap();
+        assert_eq!(body, &b"hello gzip"[..]);
+        assert!(!headers.contains_key(http::header::CONTENT_ENCODING));
+        assert!(!headers.contains_key(http::header::CONTENT_LENGTH));
+    }
+
+    #[test]
+    fn test_decode_without_encoding() {
+        let mut headers = http::HeaderMap::new();
+        let body = decode_response_body(&mut headers, Bytes::from("plain"), false, None).unwrap();
+        assert_eq!(body, &b"plain"[..]);
+    }
+
+    #[test]
+    fn test_mislabeled_plaintext_strict() {
+        let mut headers = headers_with_encoding("gzip");
+        let result = decode_response_body(&mut headers, Bytes::from("plain text"), false, None);
+        assert!(matches!(result, Err(FetchError::Network(_))));
+    }
+
+    #[test]
+    fn test_mislabeled_plaintext_lenient() {
+        let mut headers = headers_with_encoding("gzip");
+        let body =
+            decode_response_body(&mut headers, Bytes::from("plain text"), true, None).unwrap();
+        assert_eq!(body, &b"plain text"[..]);
+        assert!(!headers.contains_key(http::header::CONTENT_ENCODING));
+    }
+
+    #[test]
+    fn test_corrupt_gzip_lenient_still_errors() {
+        let mut data = gzip(b"hello gzip").to_vec();
+        data.truncate(12);
+        let mut headers = headers_with_encoding("gzip");
+        let result = decode_response_body(&mut headers, Bytes::from(data), true, None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_decode_stops_at_limit() {
+        let bomb = gzip(&vec![0u8; 1 << 20]);
+        let mut headers = headers_with_encoding("gzip");
+        let result = decode_response_body(&mut headers, bomb.clone(), false, Some(1000));
+        assert!(matches!(result, Err(FetchError::Network(_))));
+
+        let mut headers = headers_with_encoding("gzip");
+        let body = decode_response_body(&mut headers, bomb, false, Some(1 << 20)).unwrap();
+        assert_eq!(body.len(), 1 << 20);
+    }
+
+    #[test]
+    fn test_decode_brotli() {
+        let mut headers = headers_with_encoding("br");
+        let body =
+            decode_response_body(&mut headers, brotli(b"hello brotli"), false, None).unwrap();
+        assert_eq!(body, &b"hello brotli"[..]);
+        assert!(!headers.contains_key(http::header::CONTENT_ENCODING));
+    }
+
+    #[test]
+    fn test_decode_deflate() {
+        let mut encoder =
+            flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"hello zlib").unwrap();
+        let zlib = Bytes::from(encoder.finish().unwrap());
+        let mut headers = headers_with_encoding("deflate");
+        let body = decode_response_body(&mut headers, zlib, false, None).unwrap();
+        assert_eq!(body, &b"hello zlib"[..]);
+        assert!(!headers.contains_key(http::header::CONTENT_ENCODING));
+
+        // Raw deflate without the zlib wrapper is accepted as well
+        let mut encoder =
+            flate2::write::DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(b"hello raw").unwrap();
+        let raw = Bytes::from(encoder.finish().unwrap());
+        let mut headers = headers_with_encoding("deflate");
+        let body = decode_response_body(&mut headers, raw, false, None).unwrap();
+        assert_eq!(body, &b"hello raw"[..]);
+
+        let mut headers = headers_with_encoding("deflate");
+        let result = decode_response_body(&mut headers, Bytes::from("not deflate"), false, None);
+        assert!(matches!(result, Err(FetchError::Network(_))));
+    }
+
+    #[test]
+    fn test_decode_stacked_encodings() {
+        // `br, gzip` means brotli was applied first, so the body is gzip of brotli
+        let encoded = gzip(&brotli(b"doubly encoded"));
+        let mut headers = headers_with_encoding("br, gzip");
+        let body = decode_response_body(&mut headers, encoded, false, None).unwrap();
+        assert_eq!(body, &b"doubly encoded"[..]);
+        assert!(!headers.contains_key(http::header::CONTENT_ENCODING));
+        assert!(!headers.contains_key(http::header::CONTENT_LENGTH));
+    }
+
+    #[test]
+    fn test_stacked_encodings_wrong_order_errors() {
+        let encoded = gzip(&brotli(b"doubly encoded"));
+        let mut headers = headers_with_encoding("gzip, br");
+        let result = decode_response_body(&mut headers, encoded, false, None);
+        assert!(matches!(result, Err(FetchError::Network(_))));
+    }
+
+    #[test]
+    fn test_unknown_coding_strict() {
+        let mut headers = headers_with_encoding("gzip, zstd");
+        let result = decode_response_body(&mut headers, gzip(b"data"), false, None);
+        match result {
+            Err(FetchError::Network(e)) => assert!(e.message().contains("zstd")),
+            other => panic!("expected a network error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_unknown_coding_lenient() {
+        let encoded = gzip(b"data");
+        let mut headers = headers_with_encoding("gzip, zstd");
+        let body = decode_response_body(&mut headers, encoded.clone(), true, None).unwrap();
+        assert_eq!(body, encoded);
+        assert!(headers.contains_key(http::header::CONTENT_ENCODING));
+    }
+
+    #[test]
+    fn test_identity_coding_ignored() {
+        let mut headers = headers_with_encoding("identity, gzip");
+        let body = decode_response_body(&mut headers, gzip(b"plain"), false, None).unwrap();
+        assert_eq!(body, &b"plain"[..]);
+    }
+
+    #[test]
+    fn test_decode_transfer_encoding_gzip() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(
+            http::header::TRANSFER_ENCODING,
+            "gzip, chunked".parse().unwrap(),
+        );
+        let body = decode_response_body(&mut headers, gzip(b"hello te"), false, None).unwrap();
+        assert_eq!(body, &b"hello te"[..]);
+        assert_eq!(headers[http::header::TRANSFER_ENCODING], "chunked");
+    }
+
+    #[test]
+    fn test_decode_transfer_and_content_encoding() {
+        // Content coding applied first, then the transfer coding on top
+        let encoded = gzip(&brotli(b"layered"));
+        let mut headers = headers_with_encoding("br");
+        headers.insert(http::header::TRANSFER_ENCODING, "gzip".parse().unwrap());
+        let body = decode_response_body(&mut headers, encoded, false, None).unwrap();
+        assert_eq!(body, &b"layered"[..]);
+        assert!(!headers.contains_key(http::header::TRANSFER_ENCODING));
+        assert!(!headers.contains_key(http::header::CONTENT_ENCODING));
+    }
+
+    #[test]
+    fn test_chunked_transfer_encoding_untouched() {
+        let mut headers = http::HeaderMap::new();
+        headers.insert(http::header::TRANSFER_ENCODING, "chunked".parse().unwrap());
+        let body = decode_response_body(&mut headers, Bytes::from("plain"), false, None).unwrap();
+        assert_eq!(body, &b"plain"[..]);
+        assert_eq!(headers[http::header::TRANSFER_ENCODING], "chunked");
+    }
+}
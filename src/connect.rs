@@ -0,0 +1,636 @@
+//! Connector plumbing for the HTTP client.
+//!
+//! hyper opens connections through a connector service. This module wraps the
+//! default HTTPS connector so that per-connection details, such as how long the
+//! DNS lookup took, are attached to every response sent over the connection,
+//! and routes connections through an HTTP proxy when one is configured.
+
+use hyper::rt::{Read, ReadBufCursor, Write};
+use hyper::Uri;
+use hyper_util::client::legacy::connect::dns::{GaiResolver, Name};
+use hyper_util::client::legacy::connect::proxy::Tunnel;
+use hyper_util::client::legacy::connect::{Connected, Connection, HttpConnector};
+use hyper_util::client::proxy::matcher::Matcher;
+use std::cell::Cell;
+use std::future::Future;
+use std::io;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tower_service::Service;
+
+/// The HTTP connector type used by the client.
+pub(crate) type TimedHttpConnector = HttpConnector<TimingResolver>;
+
+/// The TCP connector below TLS, tunneling through a proxy when configured.
+pub(crate) type TcpConnector = ProxyConnector<TimedHttpConnector>;
+
+/// The TLS connector, backed by rustls when the `rustls` feature is enabled.
+#[cfg(feature = "rustls")]
+pub(crate) type TlsConnector = hyper_rustls::HttpsConnector<TcpConnector>;
+
+/// The TLS connector, backed by the platform's native TLS library.
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+pub(crate) type TlsConnector = hyper_tls::HttpsConnector<TcpConnector>;
+
+#[cfg(not(any(feature = "native-tls", feature = "rustls")))]
+compile_error!("fetchttp needs a TLS backend: enable the `native-tls` or `rustls` feature");
+
+/// The full connector stack used by the client.
+pub(crate) type ClientConnector = TimingConnector<TlsConnector>;
+
+/// TLS settings applied to new connections.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TlsOptions {
+    /// Accept any server certificate, skipping all verification
+    pub(crate) accept_invalid_certs: bool,
+    /// Extra PEM or DER encoded CA certificates to trust
+    pub(crate) root_certificates: Vec<Vec<u8>>,
+}
+
+/// Error for a root certificate that cannot be parsed.
+fn invalid_root_certificate() -> crate::FetchError {
+    crate::FetchError::Type(crate::TypeError::new("Invalid root certificate"))
+}
+
+/// Whether `cert` is PEM encoded rather than DER.
+fn is_pem(cert: &[u8]) -> bool {
+    cert.trim_ascii_start().starts_with(b"-----BEGIN")
+}
+
+/// Wrap an HTTP connector with TLS support for `https` URLs.
+#[cfg(feature = "rustls")]
+pub(crate) fn tls_connector(
+    http: TcpConnector,
+    options: &TlsOptions,
+) -> crate::Result<TlsConnector> {
+    let builder = hyper_rustls::HttpsConnectorBuilder::new();
+    let builder = if options.accept_invalid_certs {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let config = rustls::ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()
+            .map_err(|e| crate::FetchError::Network(crate::NetworkError::new(&e.to_string())))?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(NoCertificateVerification(provider)))
+            .with_no_client_auth();
+        builder.with_tls_config(config)
+    } else if !options.root_certificates.is_empty() {
+        use rustls::pki_types::pem::PemObject;
+        use rustls::pki_types::CertificateDer;
+
+        let mut roots = rustls::RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        for cert in &options.root_certificates {
+            let ders = if is_pem(cert) {
+                CertificateDer::pem_slice_iter(cert)
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| invalid_root_certificate())?
+            } else {
+                vec![CertificateDer::from(cert.clone())]
+            };
+            if ders.is_empty() {
+                return Err(invalid_root_certificate());
+            }
+            for der in ders {
+                roots.add(der).map_err(|_| invalid_root_certificate())?;
+            }
+        }
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let config = rustls::ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(|e| crate::FetchError::Network(crate::NetworkError::new(&e.to_string())))?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        builder.with_tls_config(config)
+    } else {
+        builder.with_webpki_roots()
+    };
+    Ok(builder.https_or_http().enable_http1().wrap_connector(http))
+}
+
+/// Wrap an HTTP connector with TLS support for `https` URLs.
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+pub(crate) fn tls_connector(
+    http: TcpConnector,
+    options: &TlsOptions,
+) -> crate::Result<TlsConnector> {
+    let mut builder = native_tls::TlsConnector::builder();
+    for cert in &options.root_certificates {
+        let certs = if is_pem(cert) {
+            // `Certificate::from_pem` only reads the first certificate
+            let blocks = pem_certificates(cert);
+            if blocks.is_empty() {
+                return Err(invalid_root_certificate());
+            }
+            blocks
+                .into_iter()
+                .map(native_tls::Certificate::from_pem)
+                .collect::<Result<Vec<_>, _>>()
+        } else {
+            native_tls::Certificate::from_der(cert).map(|cert| vec![cert])
+        };
+        for cert in certs.map_err(|_| invalid_root_certificate())? {
+            builder.add_root_certificate(cert);
+        }
+    }
+    let tls = builder
+        .danger_accept_invalid_certs(options.accept_invalid_certs)
+        .danger_accept_invalid_hostnames(options.accept_invalid_certs)
+        .build()
+        .map_err(|e| crate::FetchError::Network(crate::NetworkError::new(&e.to_string())))?;
+    Ok(hyper_tls::HttpsConnector::from((http, tls.into())))
+}
+
+/// Split a PEM bundle into its `CERTIFICATE` blocks, skipping anything else.
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+fn pem_certificates(pem: &[u8]) -> Vec<&[u8]> {
+    const BEGIN: &[u8] = b"-----BEGIN CERTIFICATE-----";
+    const END: &[u8] = b"-----END CERTIFICATE-----";
+    let find = |haystack: &[u8], needle: &[u8]| {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    };
+
+    let mut blocks = Vec::new();
+    let mut rest = pem;
+    while let Some(start) = find(rest, BEGIN) {
+        let Some(len) = find(&rest[start..], END) else {
+            break;
+        };
+        let end = start + len + END.len();
+        blocks.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    blocks
+}
+
+/// A connector that sends connections through an HTTP proxy.
+///
+/// Destinations the proxy matcher intercepts are reached through the proxy:
+/// `https` ones through a `CONNECT` tunnel, on top of which TLS is negotiated
+/// with the destination as usual, and plain `http` ones by connecting to the
+/// proxy and marking the connection so hyper sends requests in absolute form
+/// for the proxy to forward. Everything else is connected to directly.
+#[derive(Debug, Clone)]
+pub(crate) struct ProxyConnector<C> {
+    inner: C,
+    proxy: Option<Arc<Matcher>>,
+}
+
+impl<C> ProxyConnector<C> {
+    /// Wrap an existing connector, proxying the destinations `proxy` matches.
+    pub(crate) fn new(inner: C, proxy: Option<Arc<Matcher>>) -> Self {
+        Self { inner, proxy }
+    }
+}
+
+impl<C> Service<Uri> for ProxyConnector<C>
+where
+    C: Service<Uri> + Clone + Send + 'static,
+    C::Future: Send + 'static,
+    C::Response: Read + Write + Unpin + Send + 'static,
+    C::Error: Into<BoxError>,
+{
+    type Response = ProxyStream<C::Response>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let intercept = self.proxy.as_ref().and_then(|proxy| proxy.intercept(&dst));
+        let Some(intercept) = intercept else {
+            let connecting = self.inner.call(dst);
+            return Box::pin(async move {
+                let stream = connecting.await.map_err(Into::into)?;
+                Ok(ProxyStream::new(stream, false))
+            });
+        };
+
+        if dst.scheme() == Some(&http::uri::Scheme::HTTPS) {
+            let mut tunnel = Tunnel::new(intercept.uri().clone(), self.inner.clone());
+            if let Some(auth) = intercept.basic_auth() {
+                tunnel = tunnel.with_auth(auth.clone());
+            }
+            Box::pin(async move {
+                let stream = tunnel.call(dst).await?;
+                Ok(ProxyStream::new(stream, false))
+            })
+        } else {
+            let connecting = self.inner.call(intercept.uri().clone());
+            Box::pin(async move {
+                let stream = connecting.await.map_err(Into::into)?;
+                Ok(ProxyStream::new(stream, true))
+            })
+        }
+    }
+}
+
+/// A connection stream that may be a connection to a forwarding proxy.
+#[derive(Debug)]
+pub(crate) struct ProxyStream<S> {
+    inner: S,
+    /// Requests are sent to a proxy that forwards them
+    forwarding: bool,
+}
+
+impl<S> ProxyStream<S> {
+    fn new(inner: S, forwarding: bool) -> Self {
+        Self { inner, forwarding }
+    }
+}
+
+impl<S: Connection> Connection for ProxyStream<S> {
+    fn connected(&self) -> Connected {
+        self.inner.connected().proxy(self.forwarding)
+    }
+}
+
+impl<S: Read + Unpin> Read for ProxyStream<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<S: Write + Unpin> Write for ProxyStream<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+}
+
+/// A rustls certificate verifier that accepts every server certificate.
+///
+/// Handshake signatures are still checked so the connection is well formed,
+/// but nothing ties the certificate to a trusted root or to the host name.
+#[cfg(feature = "rustls")]
+#[derive(Debug)]
+struct NoCertificateVerification(Arc<rustls::crypto::CryptoProvider>);
+
+#[cfg(feature = "rustls")]
+impl rustls::client::danger::ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &rustls::pki_types::CertificateDer<'_>,
+        _intermediates: &[rustls::pki_types::CertificateDer<'_>],
+        _server_name: &rustls::pki_types::ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: rustls::pki_types::UnixTime,
+    ) -> Result<rustls::client::danger::ServerCertVerified, rustls::Error> {
+        Ok(rustls::client::danger::ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &rustls::pki_types::CertificateDer<'_>,
+        dss: &rustls::DigitallySignedStruct,
+    ) -> Result<rustls::client::danger::HandshakeSignatureValid, rustls::Error> {
+        rustls::crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
+/// Boxed error type used by connector services.
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+tokio::task_local! {
+    /// DNS lookup time recorded by the resolver for the connection being opened.
+    static DNS_TIME: Cell<Option<Duration>>;
+}
+
+/// DNS lookup time of the connection a response was received on.
+///
+/// Stored in the response extensions by hyper for every response.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct DnsTime(pub(crate) Duration);
+
+/// A DNS resolver that measures how long each lookup takes.
+///
+/// It can also refuse names that resolve to private addresses, see
+/// [`is_private_ip()`].
+#[derive(Debug, Clone)]
+pub(crate) struct TimingResolver {
+    inner: GaiResolver,
+    /// Fail lookups returning any private address
+    block_private_ips: bool,
+}
+
+impl TimingResolver {
+    /// Create a resolver backed by the system resolver.
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: GaiResolver::new(),
+            block_private_ips: false,
+        }
+    }
+
+    /// Fail lookups that return any private address.
+    pub(crate) fn block_private_ips(mut self, block: bool) -> Self {
+        self.block_private_ips = block;
+        self
+    }
+}
+
+impl Service<Name> for TimingResolver {
+    type Response = std::vec::IntoIter<SocketAddr>;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        let start = Instant::now();
+        let block_private_ips = self.block_private_ips;
+        let lookup = self.inner.call(name);
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = lookup.await?.collect();
+            // Only set when polled from within `TimingConnector`
+            let _ = DNS_TIME.try_with(|slot| slot.set(Some(start.elapsed())));
+            // Refusing the whole lookup, rather than filtering it, leaves no
+            // way for a name with mixed addresses to reach a private one
+            if let Some(addr) = addrs
+                .iter()
+                .find(|addr| block_private_ips && is_private_ip(addr.ip()))
+            {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    BlockedAddress(addr.ip()),
+                ));
+            }
+            Ok(addrs.into_iter())
+        })
+    }
+}
+
+/// Error for a connection refused because it would reach a private address.
+#[derive(Debug)]
+pub(crate) struct BlockedAddress(pub(crate) IpAddr);
+
+impl std::fmt::Display for BlockedAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Connection to private address {} blocked", self.0)
+    }
+}
+
+impl std::error::Error for BlockedAddress {}
+
+/// Whether `ip` is in a loopback, private, link-local or otherwise
+/// non-public range that user-supplied URLs should not reach.
+///
+/// Besides the RFC 1918 and unique local ranges this covers link-local
+/// addresses, home of cloud metadata endpoints, carrier-grade NAT space and
+/// the unspecified and broadcast addresses. IPv4-mapped IPv6 addresses are
+/// judged by their IPv4 address.
+pub(crate) fn is_private_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+                // 100.64.0.0/10, shared address space
+                || (a == 100 && (b & 0xc0) == 64)
+                // 0.0.0.0/8, "this network"
+                || a == 0
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(v4) => is_private_ip(IpAddr::V4(v4)),
+            None => {
+                let first = ip.segments()[0];
+                ip.is_loopback()
+                    || ip.is_unspecified()
+                    // fc00::/7, unique local
+                    || (first & 0xfe00) == 0xfc00
+                    // fe80::/10, link-local
+                    || (first & 0xffc0) == 0xfe80
+            }
+        },
+    }
+}
+
+/// A connector that tags connections with the DNS time of their lookup.
+#[derive(Debug, Clone)]
+pub(crate) struct TimingConnector<C> {
+    inner: C,
+}
+
+impl<C> TimingConnector<C> {
+    /// Wrap an existing connector.
+    pub(crate) fn new(inner: C) -> Self {
+        Self { inner }
+    }
+}
+
+impl<C> Service<Uri> for TimingConnector<C>
+where
+    C: Service<Uri>,
+    C::Future: Send + 'static,
+    C::Response: Send + 'static,
+    C::Error: Into<BoxError>,
+{
+    type Response = TimedStream<C::Response>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, dst: Uri) -> Self::Future {
+        let connecting = self.inner.call(dst);
+        Box::pin(DNS_TIME.scope(Cell::new(None), async move {
+            let stream = connecting.await.map_err(Into::into)?;
+            let dns_time = DNS_TIME.with(Cell::get);
+            Ok(TimedStream {
+                inner: stream,
+                dns_time,
+            })
+        }))
+    }
+}
+
+/// A connection stream carrying the DNS time measured while opening it.
+#[derive(Debug)]
+pub(crate) struct TimedStream<S> {
+    inner: S,
+    dns_time: Option<Duration>,
+}
+
+impl<S: Connection> Connection for TimedStream<S> {
+    fn connected(&self) -> Connected {
+        let connected = self.inner.connected();
+        match self.dns_time {
+            Some(time) => connected.extra(DnsTime(time)),
+            None => connected,
+        }
+    }
+}
+
+impl<S: Read + Unpin> Read for TimedStream<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<S: Write + Unpin> Write for TimedStream<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[io::IoSlice<'_>],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[tokio::test]
+    async fn test_timing_resolver_records_dns_time() {
+        let mut resolver = TimingResolver::new();
+        let name = Name::from_str("localhost").unwrap();
+
+        let time = DNS_TIME
+            .scope(Cell::new(None), async {
+                resolver.call(name).await.unwrap();
+                DNS_TIME.with(Cell::get)
+            })
+            .await;
+        assert!(time.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_resolver_blocks_private_ips() {
+        let mut resolver = TimingResolver::new().block_private_ips(true);
+        let name = Name::from_str("localhost").unwrap();
+        let err = resolver.call(name).await.unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+        assert!(err.get_ref().unwrap().is::<BlockedAddress>());
+    }
+
+    #[test]
+    fn test_is_private_ip() {
+        for private in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(is_private_ip(private.parse().unwrap()), "{}", private);
+        }
+        for public in [
+            "8.8.8.8",
+            "172.32.0.1",
+            "100.128.0.1",
+            "2606:4700::1111",
+            "::ffff:1.1.1.1",
+        ] {
+            assert!(!is_private_ip(public.parse().unwrap()), "{}", public);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_timing_resolver_outside_connector() {
+        let mut resolver = TimingResolver::new();
+        let name = Name::from_str("localhost").unwrap();
+
+        // Resolving without a connector scope must not panic
+        let addrs = resolver.call(name).await.unwrap();
+        assert!(addrs.count() > 0);
+    }
+}
@@ -0,0 +1,298 @@
+//! Cookie storage for [`FetchClient`](crate::FetchClient).
+//!
+//! A client built with a cookie store keeps the cookies servers set with
+//! `Set-Cookie` and sends the matching ones back in the `Cookie` header of
+//! later requests, following the storage model of RFC 6265: cookies are
+//! scoped by domain and path, expire according to `Max-Age` or `Expires`, and
+//! `Secure` cookies are only sent over HTTPS. Only requests whose credentials
+//! mode is [`RequestCredentials::Include`](crate::RequestCredentials::Include)
+//! use the store.
+
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use url::Url;
+
+/// A cookie held by a [`CookieJar`].
+#[derive(Debug, Clone)]
+struct StoredCookie {
+    name: String,
+    value: String,
+    /// Lowercase domain the cookie belongs to
+    domain: String,
+    /// Only sent to `domain` itself, not its subdomains
+    host_only: bool,
+    path: String,
+    /// Only sent over HTTPS
+    secure: bool,
+    /// `None` for a session cookie, kept as long as the jar
+    expires: Option<SystemTime>,
+}
+
+impl StoredCookie {
+    /// Parse a `Set-Cookie` header value received in response to `url`.
+    ///
+    /// Returns `None` for malformed cookies and for cookies `url` may not set.
+    fn parse(header: &str, url: &Url, now: SystemTime) -> Option<Self> {
+        let host = url.host_str()?.to_ascii_lowercase();
+        let mut parts = header.split(';');
+        let (name, value) = parts.next()?.split_once('=')?;
+        let name = name.trim();
+        if name.is_empty() {
+            return None;
+        }
+
+        let mut cookie = Self {
+            name: name.to_string(),
+            value: value.trim().to_string(),
+            domain: host.clone(),
+            host_only: true,
+            path: default_path(url),
+            secure: false,
+            expires: None,
+        };
+        let mut max_age = None;
+        let mut expires = None;
+        for attribute in parts {
+            let (key, value) = match attribute.split_once('=') {
+                Some((key, value)) => (key.trim(), value.trim()),
+                None => (attribute.trim(), ""),
+            };
+            match key.to_ascii_lowercase().as_str() {
+                "domain" if !value.is_empty() => {
+                    let domain = value.trim_start_matches('.').to_ascii_lowercase();
+                    // Refuse cookies for other sites and for whole top-level domains
+                    if !domain_matches(&host, &domain) || (!domain.contains('.') && domain != host)
+                    {
+                        return None;
+                    }
+                    cookie.domain = domain;
+                    cookie.host_only = false;
+                }
+                "path" if value.starts_with('/') => cookie.path = value.to_string(),
+                "secure" => cookie.secure = true,
+                "max-age" => {
+                    if let Ok(seconds) = value.parse::<i64>() {
+                        max_age = Some(match u64::try_from(seconds) {
+                            Ok(seconds) if seconds > 0 => {
+                                now.checked_add(Duration::from_secs(seconds))
+                            }
+                            _ => Some(SystemTime::UNIX_EPOCH),
+                        });
+                    }
+                }
+                "expires" => {
+                    if let Ok(date) = httpdate::parse_http_date(value) {
+                        expires = Some(Some(date));
+                    }
+                }
+                _ => {}
+            }
+        }
+        // Max-Age wins over Expires; an unrepresentable expiry means never
+        cookie.expires = max_age.or(expires).flatten();
+        Some(cookie)
+    }
+
+    fn is_expired(&self, now: SystemTime) -> bool {
+        self.expires.is_some_and(|expires| expires <= now)
+    }
+
+    /// Whether this cookie should be sent with a request to `url`.
+    fn matches(&self, url: &Url, now: SystemTime) -> bool {
+        let Some(host) = url.host_str() else {
+            return false;
+        };
+        let host = host.to_ascii_lowercase();
+        let domain_ok = if self.host_only {
+            host == self.domain
+        } else {
+            domain_matches(&host, &self.domain)
+        };
+        domain_ok
+            && path_matches(url.path(), &self.path)
+            && (!self.secure || url.scheme() == "https")
+            && !self.is_expired(now)
+    }
+}
+
+/// Whether `host` is `domain` or one of its subdomains.
+///
+/// IP addresses only match themselves.
+fn domain_matches(host: &str, domain: &str) -> bool {
+    if host == domain {
+        return true;
+    }
+    let is_ip = host.parse::<std::net::IpAddr>().is_ok() || host.starts_with('[');
+    !is_ip
+        && host
+            .strip_suffix(domain)
+            .is_some_and(|prefix| prefix.ends_with('.'))
+}
+
+/// Whether `request_path` is within the cookie path `cookie_path`.
+fn path_matches(request_path: &str, cookie_path: &str) -> bool {
+    request_path == cookie_path
+        || request_path
+            .strip_prefix(cookie_path)
+            .is_some_and(|rest| cookie_path.ends_with('/') || rest.starts_with('/'))
+}
+
+/// The path a cookie set without a `Path` attribute applies to: the
+/// directory of the request path.
+fn default_path(url: &Url) -> String {
+    let path = url.path();
+    match path.rfind('/') {
+        Some(0) | None => "/".to_string(),
+        Some(end) => path[..end].to_string(),
+    }
+}
+
+/// A thread-safe store of cookies shared by all clones of a client.
+#[derive(Debug, Default)]
+pub(crate) struct CookieJar {
+    cookies: Mutex<Vec<StoredCookie>>,
+}
+
+impl CookieJar {
+    /// Store the cookies from the `Set-Cookie` headers of a response to `url`.
+    pub(crate) fn store(&self, url: &Url, headers: &http::HeaderMap) {
+        let now = SystemTime::now();
+        let mut cookies = self.cookies.lock().unwrap_or_else(|e| e.into_inner());
+        for value in headers.get_all(http::header::SET_COOKIE) {
+            let Some(cookie) = value
+                .to_str()
+                .ok()
+                .and_then(|value| StoredCookie::parse(value, url, now))
+            else {
+                continue;
+            };
+            cookies.retain(|stored| {
+                stored.name != cookie.name
+                    || stored.domain != cookie.domain
+                    || stored.path != cookie.path
+            });
+            // An already expired cookie only serves to delete the old one
+            if !cookie.is_expired(now) {
+                cookies.push(cookie);
+            }
+        }
+    }
+
+    /// The `Cookie` header value to send with a request to `url`, if any.
+    ///
+    /// Cookies with longer paths come first, as RFC 6265 recommends.
+    pub(crate) fn header_for(&self, url: &Url) -> Option<String> {
+        let now = SystemTime::now();
+        let mut cookies = self.cookies.lock().unwrap_or_else(|e| e.into_inner());
+        cookies.retain(|cookie| !cookie.is_expired(now));
+
+        let mut matching: Vec<_> = cookies
+            .iter()
+            .filter(|cookie| cookie.matches(url, now))
+            .collect();
+        if matching.is_empty() {
+            return None;
+        }
+        matching.sort_by_key(|cookie| std::cmp::Reverse(cookie.path.len()));
+        let pairs: Vec<_> = matching
+            .iter()
+            .map(|cookie| format!("{}={}", cookie.name, cookie.value))
+            .collect();
+        Some(pairs.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jar_with(url: &str, set_cookies: &[&str]) -> CookieJar {
+        let jar = CookieJar::default();
+        let mut headers = http::HeaderMap::new();
+        for value in set_cookies {
+            headers.append(http::header::SET_COOKIE, value.parse().unwrap());
+        }
+        jar.store(&Url::parse(url).unwrap(), &headers);
+        jar
+    }
+
+    fn header(jar: &CookieJar, url: &str) -> Option<String> {
+        jar.header_for(&Url::parse(url).unwrap())
+    }
+
+    #[test]
+    fn test_host_only_and_domain_cookies() {
+        let jar = jar_with(
+            "https://www.example.com/",
+            &["host=1", "shared=2; Domain=.example.com"],
+        );
+        assert_eq!(
+            header(&jar, "https://www.example.com/").as_deref(),
+            Some("host=1; shared=2")
+        );
+        assert_eq!(
+            header(&jar, "https://api.example.com/").as_deref(),
+            Some("shared=2")
+        );
+        assert_eq!(header(&jar, "https://example.org/"), None);
+
+        // Cookies for another site or a whole top-level domain are refused
+        let jar = jar_with(
+            "https://www.example.com/",
+            &["a=1; Domain=example.org", "b=2; Domain=com"],
+        );
+        assert_eq!(header(&jar, "https://www.example.com/"), None);
+    }
+
+    #[test]
+    fn test_path_and_secure() {
+        let jar = jar_with(
+            "http://example.com/account/login",
+            &["dir=1", "root=2; Path=/", "secure=3; Path=/; Secure"],
+        );
+        assert_eq!(
+            header(&jar, "https://example.com/account/settings").as_deref(),
+            Some("dir=1; root=2; secure=3")
+        );
+        assert_eq!(
+            header(&jar, "http://example.com/accounts").as_deref(),
+            Some("root=2")
+        );
+    }
+
+    #[test]
+    fn test_expiry_and_replacement() {
+        let jar = jar_with(
+            "http://example.com/",
+            &[
+                "gone=1; Max-Age=0",
+                "old=1; Expires=Sun, 06 Nov 1994 08:49:37 GMT",
+                "kept=1; Max-Age=3600; Expires=Sun, 06 Nov 1994 08:49:37 GMT",
+                "session=1",
+            ],
+        );
+        assert_eq!(
+            header(&jar, "http://example.com/").as_deref(),
+            Some("kept=1; session=1")
+        );
+
+        let url = Url::parse("http://example.com/").unwrap();
+        let mut headers = http::HeaderMap::new();
+        headers.append(http::header::SET_COOKIE, "session=2".parse().unwrap());
+        headers.append(
+            http::header::SET_COOKIE,
+            "kept=; Max-Age=-1".parse().unwrap(),
+        );
+        jar.store(&url, &headers);
+        assert_eq!(
+            header(&jar, "http://example.com/").as_deref(),
+            Some("session=2")
+        );
+    }
+
+    #[test]
+    fn test_malformed_cookies_ignored() {
+        let jar = jar_with("http://example.com/", &["novalue", "=anonymous", "ok=1"]);
+        assert_eq!(header(&jar, "http://example.com/").as_deref(), Some("ok=1"));
+    }
+}
@@ -128,6 +128,58 @@ impl fmt::Display for AbortError {
 
 impl std::error::Error for AbortError {}
 
+/// An HTTP status error indicating the server answered with a non-2xx status.
+///
+/// The fetch itself succeeded, but the caller asked for a successful response.
+/// Returned by helpers such as [`fetch_json`], through
+/// [`FetchJsonError::Status`], which carry the status along so callers can
+/// branch on it.
+///
+/// [`fetch_json`]: crate::fetch_json
+///
+/// # Examples
+///
+/// ```rust
+/// use fetchttp::StatusError;
+///
+/// let error = StatusError::new(404, "Not Found");
+/// assert_eq!(error.status(), 404);
+/// println!("Error: {}", error);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StatusError {
+    status: u16,
+    status_text: String,
+}
+
+impl StatusError {
+    /// Create a new StatusError for the given status code and reason phrase.
+    pub fn new(status: u16, status_text: &str) -> Self {
+        Self {
+            status,
+            status_text: status_text.to_string(),
+        }
+    }
+
+    /// Get the HTTP status code.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Get the HTTP status reason phrase.
+    pub fn status_text(&self) -> &str {
+        &self.status_text
+    }
+}
+
+impl fmt::Display for StatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "StatusError: {} {}", self.status, self.status_text)
+    }
+}
+
+impl std::error::Error for StatusError {}
+
 /// The main error type for fetch operations.
 ///
 /// This enum encompasses all possible errors that can occur during fetch operations.
@@ -165,7 +217,10 @@ impl std::error::Error for AbortError {}
 ///     }
 /// }
 /// ```
-#[derive(Debug)]
+///
+/// `FetchError` is `Clone`, so an error can be kept, for example as the last
+/// failure seen by a retry loop, and still be returned to the caller.
+#[derive(Debug, Clone)]
 pub enum FetchError {
     /// Type-related error (invalid arguments, validation failures)
     Type(TypeError),
@@ -245,6 +300,64 @@ impl From<serde_json::Error> for FetchError {
     }
 }
 
+/// The error returned by [`fetch_json`].
+///
+/// [`fetch_json`] fails either like [`fetch`] does or because the response
+/// was not successful. The second case is kept separate so it can carry the
+/// status without changing [`FetchError`].
+///
+/// [`fetch`]: crate::fetch
+/// [`fetch_json`]: crate::fetch_json
+///
+/// # Examples
+///
+/// ```rust
+/// use fetchttp::{FetchJsonError, StatusError};
+///
+/// let error = FetchJsonError::from(StatusError::new(404, "Not Found"));
+/// match error {
+///     FetchJsonError::Status(e) => assert_eq!(e.status(), 404),
+///     FetchJsonError::Fetch(e) => eprintln!("Fetch failed: {}", e),
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub enum FetchJsonError {
+    /// The fetch failed, or the body was not the expected JSON
+    Fetch(FetchError),
+    /// The response status was not in the 200-299 range
+    Status(StatusError),
+}
+
+impl fmt::Display for FetchJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fetch(e) => write!(f, "{}", e),
+            Self::Status(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for FetchJsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Fetch(e) => Some(e),
+            Self::Status(e) => Some(e),
+        }
+    }
+}
+
+impl From<FetchError> for FetchJsonError {
+    fn from(err: FetchError) -> Self {
+        Self::Fetch(err)
+    }
+}
+
+impl From<StatusError> for FetchJsonError {
+    fn from(err: StatusError) -> Self {
+        Self::Status(err)
+    }
+}
+
 /// Convenient Result type alias for fetch operations.
 ///
 /// This type alias provides a shorter way to write `Result<T, FetchError>`.
@@ -277,6 +390,9 @@ mod tests {
 
         let abort_error = AbortError::new("aborted");
         assert_eq!(format!("{}", abort_error), "AbortError: aborted");
+
+        let status_error = StatusError::new(404, "Not Found");
+        assert_eq!(format!("{}", status_error), "StatusError: 404 Not Found");
     }
 
     #[test]
@@ -292,6 +408,38 @@ mod tests {
         let abort_error = AbortError::new("test");
         let fetch_error: FetchError = abort_error.into();
         assert!(matches!(fetch_error, FetchError::Abort(_)));
+
+        let status_error = StatusError::new(500, "Internal Server Error");
+        let json_error: FetchJsonError = status_error.into();
+        assert!(matches!(json_error, FetchJsonError::Status(_)));
+        assert_eq!(
+            json_error.to_string(),
+            "StatusError: 500 Internal Server Error"
+        );
+
+        let json_error: FetchJsonError = FetchError::Abort(AbortError::new("test")).into();
+        assert!(matches!(
+            json_error,
+            FetchJsonError::Fetch(FetchError::Abort(_))
+        ));
+    }
+
+    #[test]
+    fn test_fetch_error_clone() {
+        let errors = [
+            FetchError::Type(TypeError::new("bad input")),
+            FetchError::Network(NetworkError::new("reset")),
+            FetchError::Abort(AbortError::new("aborted")),
+        ];
+
+        for error in &errors {
+            let cloned = error.clone();
+            assert_eq!(cloned.to_string(), error.to_string());
+            assert_eq!(
+                std::mem::discriminant(&cloned),
+                std::mem::discriminant(error)
+            );
+        }
     }
 
     #[test]
@@ -5,7 +5,19 @@
 //! all standard header operations.
 
 use crate::error::{FetchError, Result, TypeError};
-use std::collections::HashMap;
+use indexmap::IndexMap;
+use std::fmt;
+
+/// Maximum number of characters of a header value shown by `Debug`.
+const DEBUG_VALUE_LIMIT: usize = 64;
+
+/// Headers whose values are never shown by `Debug`.
+const REDACTED_HEADERS: [&str; 4] = [
+    "authorization",
+    "cookie",
+    "proxy-authorization",
+    "set-cookie",
+];
 
 /// HTTP headers container following the WHATWG Fetch specification.
 ///
@@ -29,11 +41,31 @@ use std::collections::HashMap;
 /// headers.append("Accept", "text/plain").unwrap();
 /// assert_eq!(headers.get("accept").unwrap().unwrap(), "application/json, text/plain");
 /// ```
-#[derive(Debug, Clone, Default)]
+///
+/// The `Debug` output is meant for logs: credential-bearing headers
+/// (`Authorization`, `Proxy-Authorization`, `Cookie` and `Set-Cookie`) are
+/// redacted and values longer than 64 characters are cut off and end in
+/// `...`, so large tokens do not flood logs.
+///
+/// By default headers are iterated, and sent, sorted by name in
+/// byte-lexicographic order, as the Fetch specification requires. The order
+/// is deterministic, so it can be relied on when building a canonical string
+/// for request signing. Headers created with [`ordered()`](Headers::ordered)
+/// instead keep the order in which they were first set, for servers that
+/// fingerprint clients by header order.
+///
+/// Every value of a repeated header is kept separately: [`get()`](Headers::get)
+/// joins them with `", "`, while [`entries()`](Headers::entries) and
+/// [`get_set_cookie()`](Headers::get_set_cookie) return them one by one, so
+/// `Set-Cookie` values, which may themselves contain commas, are never merged.
+#[derive(Clone, Default)]
 pub struct Headers {
-    /// Internal map storing header name-value pairs.
-    /// Names are stored in lowercase for case-insensitive access.
-    map: HashMap<String, String>,
+    /// Internal map from header names to their values, in the order added.
+    /// Names are stored in lowercase for case-insensitive access, and kept
+    /// sorted unless `ordered` is set.
+    map: IndexMap<String, Vec<String>>,
+    /// Keep headers in insertion order, including across deletions
+    ordered: bool,
 }
 
 impl Headers {
@@ -51,10 +83,111 @@ impl Headers {
         Self::default()
     }
 
+    /// Create a new empty Headers instance that preserves insertion order.
+    ///
+    /// Iteration, and the order of the headers on the wire when sent with a
+    /// request, follows the order in which each header name was first set or
+    /// appended. Replacing a header's value with [`set()`](Headers::set)
+    /// keeps its position, and deleting a header leaves the others in place.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::Headers;
+    ///
+    /// let mut headers = Headers::ordered();
+    /// headers.set("User-Agent", "my-client/1.0").unwrap();
+    /// headers.set("Accept", "*/*").unwrap();
+    /// headers.set("Accept-Language", "en-US").unwrap();
+    ///
+    /// let names: Vec<_> = headers.keys().collect();
+    /// assert_eq!(names, ["user-agent", "accept", "accept-language"]);
+    /// ```
+    pub fn ordered() -> Self {
+        Self {
+            ordered: true,
+            ..Self::default()
+        }
+    }
+
+    /// Whether these headers preserve insertion order.
+    ///
+    /// See [`ordered()`](Headers::ordered).
+    pub fn is_ordered(&self) -> bool {
+        self.ordered
+    }
+
+    /// Parse a raw HTTP header block into `Headers`.
+    ///
+    /// This is the inverse of [`to_wire_string()`](Headers::to_wire_string) and
+    /// is handy for header blocks captured in logs or test fixtures. Lines are
+    /// `Name: Value` pairs separated by CRLF or LF; blank lines are ignored.
+    /// Obsolete line folding is supported: a line starting with a space or tab
+    /// continues the previous header's value. Repeated headers are combined as
+    /// with [`append()`](Headers::append), and each `Set-Cookie` line is kept
+    /// as its own cookie for [`get_set_cookie()`](Headers::get_set_cookie).
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TypeError`] for a line without a colon, whitespace before
+    /// the colon, a continuation line with no header to continue, or an
+    /// invalid header name or value.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::Headers;
+    ///
+    /// let headers = Headers::parse("Content-Type: text/html\r\nX-Long: part one\r\n  part two\r\n").unwrap();
+    /// assert_eq!(headers.get("content-type").unwrap().unwrap(), "text/html");
+    /// assert_eq!(headers.get("x-long").unwrap().unwrap(), "part one part two");
+    /// ```
+    pub fn parse(raw: &str) -> Result<Headers> {
+        let mut fields: Vec<(String, String)> = Vec::new();
+
+        for line in raw.split('\n') {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            if line.starts_with([' ', '\t']) {
+                let (_, value) = fields.last_mut().ok_or_else(|| {
+                    FetchError::Type(TypeError::new("Header continuation line without a header"))
+                })?;
+                let continuation = line.trim();
+                if value.is_empty() {
+                    value.push_str(continuation);
+                } else {
+                    value.push(' ');
+                    value.push_str(continuation);
+                }
+                continue;
+            }
+
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| FetchError::Type(TypeError::new("Invalid header line")))?;
+            if name.ends_with([' ', '\t']) {
+                return Err(FetchError::Type(TypeError::new(
+                    "Whitespace before header colon",
+                )));
+            }
+            fields.push((name.to_string(), value.trim().to_string()));
+        }
+
+        let mut headers = Headers::new();
+        for (name, value) in fields {
+            headers.append(&name, &value)?;
+        }
+        Ok(headers)
+    }
+
     /// Append a value to an existing header or create a new one.
     ///
-    /// If the header already exists, the new value is appended with a comma
-    /// separator. If it doesn't exist, a new header is created.
+    /// If the header already exists, the new value is added after its current
+    /// values; [`get()`](Headers::get) returns them joined with a comma. If it
+    /// doesn't exist, a new header is created.
     ///
     /// # Arguments
     ///
@@ -80,13 +213,9 @@ impl Headers {
         let name = self.validate_name(name)?;
         let value = self.validate_value(value)?;
 
-        match self.map.get(&name) {
-            Some(existing) => {
-                self.map.insert(name, format!("{}, {}", existing, value));
-            }
-            None => {
-                self.map.insert(name, value);
-            }
+        match self.map.get_mut(&name) {
+            Some(values) => values.push(value),
+            None => self.insert(name, vec![value]),
         }
         Ok(())
     }
@@ -118,7 +247,7 @@ impl Headers {
     /// ```
     pub fn delete(&mut self, name: &str) -> Result<()> {
         let name = self.validate_name(name)?;
-        self.map.remove(&name);
+        self.map.shift_remove(&name);
         Ok(())
     }
 
@@ -153,14 +282,16 @@ impl Headers {
     /// ```
     pub fn get(&self, name: &str) -> Result<Option<String>> {
         let name = self.validate_name(name)?;
-        Ok(self.map.get(&name).cloned())
+        Ok(self.map.get(&name).map(|values| values.join(", ")))
     }
 
     /// Get all Set-Cookie header values.
     ///
     /// The Set-Cookie header is special because it can have multiple values
-    /// that shouldn't be combined with commas. This method returns all values
-    /// as separate strings.
+    /// that shouldn't be combined with commas: cookie attributes such as
+    /// `Expires=Wed, 21 Oct 2026 07:28:00 GMT` contain commas themselves.
+    /// Values are never split, so this returns exactly the `Set-Cookie` lines
+    /// a response carried, or the values set and appended, in order.
     ///
     /// # Returns
     ///
@@ -172,16 +303,15 @@ impl Headers {
     /// use fetchttp::Headers;
     ///
     /// let mut headers = Headers::new();
-    /// headers.set("Set-Cookie", "session=abc123, secure=true").unwrap();
+    /// headers.append("Set-Cookie", "session=abc123; Expires=Wed, 21 Oct 2026 07:28:00 GMT").unwrap();
+    /// headers.append("Set-Cookie", "theme=dark").unwrap();
     ///
     /// let cookies = headers.get_set_cookie();
     /// assert_eq!(cookies.len(), 2);
+    /// assert_eq!(cookies[1], "theme=dark");
     /// ```
     pub fn get_set_cookie(&self) -> Vec<String> {
-        self.map
-            .get("set-cookie")
-            .map(|v| v.split(", ").map(|s| s.to_string()).collect())
-            .unwrap_or_default()
+        self.map.get("set-cookie").cloned().unwrap_or_default()
     }
 
     /// Check if a header exists.
@@ -240,13 +370,27 @@ impl Headers {
     pub fn set(&mut self, name: &str, value: &str) -> Result<()> {
         let name = self.validate_name(name)?;
         let value = self.validate_value(value)?;
-        self.map.insert(name, value);
+        self.insert(name, vec![value]);
         Ok(())
     }
 
+    /// Insert validated header values, keeping the map in the order of this
+    /// mode.
+    fn insert(&mut self, name: String, values: Vec<String>) {
+        if self.ordered {
+            self.map.insert(name, values);
+        } else {
+            self.map.insert_sorted(name, values);
+        }
+    }
+
     /// Iterate over all header name-value pairs.
     ///
-    /// Returns an iterator that yields tuples of (name, value) for all headers.
+    /// Returns an iterator that yields tuples of (name, value) for all headers,
+    /// sorted by name, or in insertion order for
+    /// [`ordered()`](Headers::ordered) headers. A header with several values
+    /// yields one tuple per value, in the order they were added; use
+    /// [`get()`](Headers::get) for the combined value.
     ///
     /// # Examples
     ///
@@ -257,15 +401,21 @@ impl Headers {
     /// headers.set("Content-Type", "application/json").unwrap();
     /// headers.set("Accept", "application/json").unwrap();
     ///
-    /// for (name, value) in headers.entries() {
-    ///     println!("{}: {}", name, value);
-    /// }
+    /// let entries: Vec<_> = headers.entries().collect();
+    /// assert_eq!(
+    ///     entries,
+    ///     [("accept", "application/json"), ("content-type", "application/json")]
+    /// );
     /// ```
     pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
-        self.map.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+        self.map
+            .iter()
+            .flat_map(|(k, values)| values.iter().map(move |v| (k.as_str(), v.as_str())))
     }
 
-    /// Iterate over all header names.
+    /// Iterate over all header names, in the same order as
+    /// [`entries()`](Headers::entries), so a name is repeated for each of its
+    /// values.
     ///
     /// # Examples
     ///
@@ -277,13 +427,14 @@ impl Headers {
     /// headers.set("Accept", "application/json").unwrap();
     ///
     /// let names: Vec<_> = headers.keys().collect();
-    /// assert_eq!(names.len(), 2);
+    /// assert_eq!(names, ["accept", "content-type"]);
     /// ```
     pub fn keys(&self) -> impl Iterator<Item = &str> {
-        self.map.keys().map(|k| k.as_str())
+        self.entries().map(|(k, _)| k)
     }
 
-    /// Iterate over all header values.
+    /// Iterate over all header values, in the same order as
+    /// [`entries()`](Headers::entries).
     ///
     /// # Examples
     ///
@@ -292,13 +443,95 @@ impl Headers {
     ///
     /// let mut headers = Headers::new();
     /// headers.set("Content-Type", "application/json").unwrap();
-    /// headers.set("Accept", "application/json").unwrap();
+    /// headers.set("Accept", "*/*").unwrap();
     ///
     /// let values: Vec<_> = headers.values().collect();
-    /// assert_eq!(values.len(), 2);
+    /// assert_eq!(values, ["*/*", "application/json"]);
     /// ```
     pub fn values(&self) -> impl Iterator<Item = &str> {
-        self.map.values().map(|v| v.as_str())
+        self.entries().map(|(_, v)| v)
+    }
+
+    /// Call `f` with each header name and its combined value.
+    ///
+    /// This mirrors `Headers.forEach` in the web API: names are visited in
+    /// sorted order, whether or not the headers are
+    /// [`ordered()`](Headers::ordered), and the values of a repeated header
+    /// are joined with `", "` as [`get()`](Headers::get) returns them.
+    /// `Set-Cookie` is left out, since its values cannot be combined; read
+    /// them with [`get_set_cookie()`](Headers::get_set_cookie).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::Headers;
+    ///
+    /// let mut headers = Headers::ordered();
+    /// headers.set("X-Trace", "abc").unwrap();
+    /// headers.append("Accept", "text/html").unwrap();
+    /// headers.append("Accept", "*/*").unwrap();
+    /// headers.append("Set-Cookie", "a=1").unwrap();
+    ///
+    /// let mut lines = Vec::new();
+    /// headers.for_each(|name, value| lines.push(format!("{}: {}", name, value)));
+    /// assert_eq!(lines, ["accept: text/html, */*", "x-trace: abc"]);
+    /// ```
+    pub fn for_each<F: FnMut(&str, &str)>(&self, mut f: F) {
+        let mut names: Vec<_> = self.map.keys().collect();
+        names.sort();
+        for name in names {
+            if name != "set-cookie" {
+                f(name, &self.map[name].join(", "));
+            }
+        }
+    }
+
+    /// Render the headers as an HTTP/1.1 header block.
+    ///
+    /// Each header value becomes a `name: value\r\n` line, sorted by name as
+    /// the Fetch specification requires for iteration. Values of a repeated
+    /// header are emitted on lines of their own, in the order they were
+    /// added, so [`parse()`](Headers::parse) gives back equal headers. The
+    /// block does not include the blank line that terminates the header
+    /// section.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::Headers;
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set("Content-Type", "text/plain").unwrap();
+    /// headers.set("Accept", "*/*").unwrap();
+    ///
+    /// assert_eq!(
+    ///     headers.to_wire_string(),
+    ///     "accept: */*\r\ncontent-type: text/plain\r\n"
+    /// );
+    /// ```
+    pub fn to_wire_string(&self) -> String {
+        let mut names: Vec<_> = self.map.keys().collect();
+        names.sort();
+
+        let mut wire = String::new();
+        for name in names {
+            for value in &self.map[name] {
+                wire.push_str(&format!("{}: {}\r\n", name, value));
+            }
+        }
+        wire
+    }
+
+    /// Estimate the bytes of heap memory held by the header names and values.
+    pub(crate) fn approx_heap_size(&self) -> usize {
+        self.map
+            .iter()
+            .map(|(name, values)| {
+                name.capacity()
+                    + values.capacity() * std::mem::size_of::<String>()
+                    + values.iter().map(String::capacity).sum::<usize>()
+            })
+            .sum()
     }
 
     /// Validate a header name according to HTTP standards.
@@ -340,15 +573,17 @@ impl Headers {
     /// Convert to hyper's HeaderMap for internal use.
     ///
     /// This method is used internally to convert our Headers type to hyper's
-    /// HeaderMap for HTTP requests.
+    /// HeaderMap for HTTP requests. The map iterates, and hyper writes it, in
+    /// the same order as these headers, with repeated headers sent as
+    /// separate fields.
     pub(crate) fn to_http_headers(&self) -> Result<http::HeaderMap> {
         let mut map = http::HeaderMap::new();
-        for (name, value) in &self.map {
+        for (name, value) in self.entries() {
             let header_name = http::header::HeaderName::from_bytes(name.as_bytes())
                 .map_err(|_| FetchError::Type(TypeError::new("Invalid header name")))?;
             let header_value = http::header::HeaderValue::from_str(value)
                 .map_err(|_| FetchError::Type(TypeError::new("Invalid header value")))?;
-            map.insert(header_name, header_value);
+            map.append(header_name, header_value);
         }
         Ok(map)
     }
@@ -356,15 +591,52 @@ impl Headers {
     /// Create Headers from hyper's HeaderMap.
     ///
     /// This method is used internally to convert hyper's HeaderMap to our
-    /// Headers type for HTTP responses.
+    /// Headers type for HTTP responses. Every field of a repeated header is
+    /// kept; values that are not visible ASCII are skipped.
     pub(crate) fn from_http_headers(headers: &http::HeaderMap) -> Self {
-        let mut map = HashMap::new();
-        for (name, value) in headers {
-            if let Ok(value_str) = value.to_str() {
-                map.insert(name.as_str().to_ascii_lowercase(), value_str.to_string());
+        let mut result = Self::new();
+        for name in headers.keys() {
+            let values: Vec<String> = headers
+                .get_all(name)
+                .iter()
+                .filter_map(|value| value.to_str().ok())
+                .map(str::to_string)
+                .collect();
+            if !values.is_empty() {
+                result.insert(name.as_str().to_ascii_lowercase(), values);
             }
         }
-        Self { map }
+        result
+    }
+}
+
+// Equality ignores order and mode, as the Fetch specification compares header lists by content
+impl PartialEq for Headers {
+    fn eq(&self, other: &Self) -> bool {
+        self.map == other.map
+    }
+}
+
+impl Eq for Headers {}
+
+impl fmt::Debug for Headers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names: Vec<_> = self.map.keys().collect();
+        names.sort();
+
+        let mut map = f.debug_map();
+        for name in names {
+            let value = self.map[name].join(", ");
+            if REDACTED_HEADERS.contains(&name.as_str()) {
+                map.entry(name, &"[redacted]");
+            } else if value.chars().count() > DEBUG_VALUE_LIMIT {
+                let truncated: String = value.chars().take(DEBUG_VALUE_LIMIT).collect();
+                map.entry(name, &format!("{}...", truncated));
+            } else {
+                map.entry(name, &value);
+            }
+        }
+        map.finish()
     }
 }
 
@@ -453,19 +725,79 @@ mod tests {
         headers.set("c", "3").unwrap();
 
         let entries: Vec<_> = headers.entries().collect();
-        assert_eq!(entries.len(), 3);
+        assert_eq!(entries, [("a", "1"), ("b", "2"), ("c", "3")]);
 
         let keys: Vec<_> = headers.keys().collect();
-        assert_eq!(keys.len(), 3);
-        assert!(keys.contains(&"a"));
-        assert!(keys.contains(&"b"));
-        assert!(keys.contains(&"c"));
+        assert_eq!(keys, ["a", "b", "c"]);
 
         let values: Vec<_> = headers.values().collect();
-        assert_eq!(values.len(), 3);
-        assert!(values.contains(&"1"));
-        assert!(values.contains(&"2"));
-        assert!(values.contains(&"3"));
+        assert_eq!(values, ["1", "2", "3"]);
+    }
+
+    #[test]
+    fn test_headers_sorted_iteration() {
+        let mut headers = Headers::new();
+        for name in ["X-Trace", "accept", "Content-Type", "x-a", "authorization"] {
+            headers.set(name, "1").unwrap();
+        }
+        headers.delete("content-type").unwrap();
+        headers.append("X-Trace", "2").unwrap();
+        headers.set("Accept-Language", "en").unwrap();
+
+        let sorted = [
+            "accept",
+            "accept-language",
+            "authorization",
+            "x-a",
+            "x-trace",
+        ];
+        let keys: Vec<_> = headers.keys().collect();
+        assert_eq!(keys[..5], sorted);
+        assert_eq!(keys[5], "x-trace");
+        assert_eq!(
+            headers.entries().skip(4).collect::<Vec<_>>(),
+            [("x-trace", "1"), ("x-trace", "2")]
+        );
+
+        // Requests are sent in the same order
+        let map = headers.to_http_headers().unwrap();
+        let names: Vec<_> = map.keys().map(|name| name.as_str()).collect();
+        assert_eq!(names, sorted);
+
+        let mut raw = http::HeaderMap::new();
+        raw.insert("x-b", "1".parse().unwrap());
+        raw.insert("content-length", "0".parse().unwrap());
+        raw.insert("age", "5".parse().unwrap());
+        let keys: Vec<_> = Headers::from_http_headers(&raw)
+            .keys()
+            .map(String::from)
+            .collect();
+        assert_eq!(keys, ["age", "content-length", "x-b"]);
+    }
+
+    #[test]
+    fn test_ordered_headers() {
+        let mut headers = Headers::ordered();
+        assert!(headers.is_ordered());
+        assert!(!Headers::new().is_ordered());
+
+        for name in ["x-z", "accept", "x-a", "user-agent", "x-m"] {
+            headers.set(name, "1").unwrap();
+        }
+        headers.delete("accept").unwrap();
+        headers.set("X-Z", "2").unwrap();
+        headers.append("x-a", "3").unwrap();
+
+        let map = headers.to_http_headers().unwrap();
+        let names: Vec<_> = map.keys().map(|name| name.as_str()).collect();
+        assert_eq!(names, ["x-z", "x-a", "user-agent", "x-m"]);
+        assert_eq!(map["x-z"], "2");
+        assert_eq!(map.get_all("x-a").iter().collect::<Vec<_>>(), ["1", "3"]);
+
+        let mut expected = Headers::from(&[("x-m", "1"), ("x-z", "2"), ("user-agent", "1")]);
+        expected.append("x-a", "1").unwrap();
+        expected.append("x-a", "3").unwrap();
+        assert_eq!(headers, expected);
     }
 
     #[test]
@@ -498,16 +830,180 @@ mod tests {
         assert_eq!(headers.get("accept").unwrap().unwrap(), "application/json");
     }
 
+    #[test]
+    fn test_to_wire_string() {
+        let mut headers = Headers::new();
+        headers.set("X-Trace", "abc").unwrap();
+        headers.set("Accept", "application/json").unwrap();
+        headers.append("Accept", "text/plain").unwrap();
+        headers.append("Set-Cookie", "a=1").unwrap();
+        headers.append("Set-Cookie", "b=2").unwrap();
+
+        assert_eq!(
+            headers.to_wire_string(),
+            "accept: application/json\r\n\
+             accept: text/plain\r\n\
+             set-cookie: a=1\r\n\
+             set-cookie: b=2\r\n\
+             x-trace: abc\r\n"
+        );
+        assert_eq!(Headers::parse(&headers.to_wire_string()).unwrap(), headers);
+
+        assert_eq!(Headers::new().to_wire_string(), "");
+    }
+
+    #[test]
+    fn test_debug_redacts_and_truncates() {
+        let mut headers = Headers::new();
+        headers.set("Authorization", "Bearer secret-token").unwrap();
+        headers.set("X-Long", &"a".repeat(100)).unwrap();
+        headers.set("Accept", "text/plain").unwrap();
+
+        let debug = format!("{:?}", headers);
+        assert!(!debug.contains("secret-token"));
+        assert!(debug.contains(r#""authorization": "[redacted]""#));
+        assert!(debug.contains(&format!(r#""x-long": "{}...""#, "a".repeat(64))));
+        assert!(!debug.contains(&"a".repeat(65)));
+        assert!(debug.contains(r#""accept": "text/plain""#));
+    }
+
     #[test]
     fn test_get_set_cookie() {
+        let expiring = "session=abc123; Expires=Wed, 21 Oct 2026 07:28:00 GMT";
         let mut headers = Headers::new();
-        headers
-            .set("set-cookie", "session=abc123, secure=true")
-            .unwrap();
-
-        let cookies = headers.get_set_cookie();
-        assert_eq!(cookies.len(), 2);
-        assert!(cookies.contains(&"session=abc123".to_string()));
-        assert!(cookies.contains(&"secure=true".to_string()));
+        headers.append("set-cookie", expiring).unwrap();
+        headers.append("Set-Cookie", "secure=true").unwrap();
+
+        assert_eq!(headers.get_set_cookie(), [expiring, "secure=true"]);
+        assert_eq!(
+            headers.get("set-cookie").unwrap().unwrap(),
+            format!("{}, secure=true", expiring)
+        );
+
+        headers.set("set-cookie", "theme=dark").unwrap();
+        assert_eq!(headers.get_set_cookie(), ["theme=dark"]);
+        assert!(Headers::new().get_set_cookie().is_empty());
+    }
+
+    #[test]
+    fn test_for_each() {
+        let mut headers = Headers::ordered();
+        headers.set("x-b", "2").unwrap();
+        headers.append("Set-Cookie", "a=1").unwrap();
+        headers.append("Set-Cookie", "b=2").unwrap();
+        headers.append("Accept", "text/html").unwrap();
+        headers.append("accept", "*/*").unwrap();
+        headers.set("X-A", "1").unwrap();
+
+        let mut seen = Vec::new();
+        headers.for_each(|name, value| seen.push((name.to_string(), value.to_string())));
+        assert_eq!(
+            seen,
+            [
+                ("accept".to_string(), "text/html, */*".to_string()),
+                ("x-a".to_string(), "1".to_string()),
+                ("x-b".to_string(), "2".to_string()),
+            ]
+        );
+
+        let mut calls = 0;
+        Headers::new().for_each(|_, _| calls += 1);
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn test_to_http_headers_appends() {
+        let mut headers = Headers::new();
+        headers.append("Accept", "application/json").unwrap();
+        headers.append("accept", "text/plain").unwrap();
+        headers.set("Content-Type", "text/plain").unwrap();
+
+        let map = headers.to_http_headers().unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(
+            map.get_all("accept").iter().collect::<Vec<_>>(),
+            ["application/json", "text/plain"]
+        );
+        assert_eq!(map.get_all("content-type").iter().count(), 1);
+    }
+
+    #[test]
+    fn test_http_headers_round_trip() {
+        let mut raw = http::HeaderMap::new();
+        raw.append(
+            "set-cookie",
+            "a=1; Expires=Wed, 21 Oct 2026 07:28:00 GMT"
+                .parse()
+                .unwrap(),
+        );
+        raw.append("set-cookie", "b=2".parse().unwrap());
+        raw.append("vary", "accept".parse().unwrap());
+        raw.append("vary", "accept-encoding".parse().unwrap());
+
+        let headers = Headers::from_http_headers(&raw);
+        assert_eq!(headers.get_set_cookie().len(), 2);
+        assert_eq!(
+            headers.get("vary").unwrap().unwrap(),
+            "accept, accept-encoding"
+        );
+        assert_eq!(headers.to_http_headers().unwrap(), raw);
+
+        // Values that are not visible ASCII are dropped, the others kept
+        let mut raw = http::HeaderMap::new();
+        raw.append("x-foo", "first".parse().unwrap());
+        raw.append(
+            "x-foo",
+            http::HeaderValue::from_bytes(b"caf\xc3\xa9").unwrap(),
+        );
+        raw.append("x-foo", "last".parse().unwrap());
+        raw.append("x-bar", http::HeaderValue::from_bytes(b"\xff").unwrap());
+        let headers = Headers::from_http_headers(&raw);
+        assert_eq!(headers.get("x-foo").unwrap().unwrap(), "first, last");
+        assert!(!headers.has("x-bar").unwrap());
+    }
+
+    #[test]
+    fn test_parse_header_block() {
+        let raw = "Content-Type: application/json\r\n\
+                   Set-Cookie: session=abc; Path=/; Expires=Wed, 21 Oct 2026 07:28:00 GMT\r\n\
+                   X-Folded: first\r\n\
+                   \t second\r\n\
+                   Set-Cookie: theme=dark\n\
+                   Accept: text/html\r\n\
+                   Accept: */*\r\n\
+                   \r\n";
+        let headers = Headers::parse(raw).unwrap();
+
+        assert_eq!(
+            headers.get("content-type").unwrap().unwrap(),
+            "application/json"
+        );
+        assert_eq!(headers.get("x-folded").unwrap().unwrap(), "first second");
+        assert_eq!(headers.get("accept").unwrap().unwrap(), "text/html, */*");
+        assert_eq!(
+            headers.get_set_cookie(),
+            vec![
+                "session=abc; Path=/; Expires=Wed, 21 Oct 2026 07:28:00 GMT".to_string(),
+                "theme=dark".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let mut headers = Headers::new();
+        headers.set("Content-Type", "text/plain").unwrap();
+        headers.set("X-Request-Id", "42").unwrap();
+
+        assert_eq!(Headers::parse(&headers.to_wire_string()).unwrap(), headers);
+    }
+
+    #[test]
+    fn test_parse_invalid_lines() {
+        assert!(Headers::parse("no colon here").is_err());
+        assert!(Headers::parse(" leading continuation").is_err());
+        assert!(Headers::parse("Name : value").is_err());
+        assert!(Headers::parse("Bad Name: value").is_err());
+        assert!(Headers::parse("HTTP/1.1 200 OK\r\nA: b").is_err());
     }
 }
@@ -21,11 +21,11 @@
 //!
 //! ### Simple GET Request
 //!
-//! ```rust
+//! ```rust,no_run
 //! use fetchttp::*;
 //!
 //! #[tokio::main]
-//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
 //!     let response = fetch("https://api.github.com/users/octocat", None).await?;
 //!     
 //!     if response.ok() {
@@ -39,12 +39,12 @@
 //!
 //! ### POST Request with JSON
 //!
-//! ```rust
+//! ```rust,no_run
 //! use fetchttp::*;
 //! use serde_json::json;
 //!
 //! #[tokio::main]
-//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
 //!     let data = json!({
 //!         "name": "John Doe",
 //!         "email": "john@example.com"
@@ -66,11 +66,11 @@
 //!
 //! ### Custom Headers
 //!
-//! ```rust
+//! ```rust,no_run
 //! use fetchttp::*;
 //!
 //! #[tokio::main]
-//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
 //!     let mut headers = Headers::new();
 //!     headers.set("Authorization", "Bearer your-token")?;
 //!     headers.set("User-Agent", "MyApp/1.0")?;
@@ -110,22 +110,38 @@
 mod abort;
 mod body;
 mod client;
+#[cfg(feature = "compression")]
+mod compression;
+mod connect;
+mod cookie;
 mod error;
 mod headers;
 mod request;
 mod response;
+mod retry;
+#[cfg(feature = "testing")]
+pub mod testing;
+mod upload;
 
 // Re-export all public types and functions
 pub use abort::{AbortController, AbortSignal};
 pub use body::ReadableStream;
-pub use client::fetch;
-pub use error::{AbortError, FetchError, NetworkError, Result, TypeError};
+pub use client::{
+    fetch, fetch_all, fetch_json, fetch_traced, fetch_until, fetch_with_client, send,
+    set_default_timeout, FetchClient, FetchClientBuilder,
+};
+pub use error::{
+    AbortError, FetchError, FetchJsonError, NetworkError, Result, StatusError, TypeError,
+};
 pub use headers::Headers;
 pub use request::{
-    Request, RequestCache, RequestCredentials, RequestInit, RequestMode, RequestRedirect,
+    validate_url, EarlyHintsCallback, Request, RequestCache, RequestCredentials, RequestInit,
+    RequestMode, RequestRedirect, SerializableRequest,
 };
-pub use response::{Response, ResponseInit, ResponseType};
+pub use response::{Hsts, Response, ResponseInit, ResponseType, TransferFraming};
+pub use retry::RetryPolicy;
 
 // Re-export commonly used external types
 pub use bytes::Bytes;
 pub use serde_json::{Map as JsonMap, Value as JsonValue};
+pub use url::Url;
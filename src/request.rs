@@ -57,17 +57,27 @@
 
 use crate::error::{FetchError, Result, TypeError};
 use crate::{AbortSignal, Headers, ReadableStream};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
 use url::Url;
 
+/// Callback invoked with the headers of a `103 Early Hints` response.
+///
+/// See [`RequestInit::on_early_hints`].
+pub type EarlyHintsCallback = Arc<dyn Fn(&Headers) + Send + Sync>;
+
 /// CORS mode for requests.
 ///
 /// This enum specifies how cross-origin requests should be handled, following
 /// the WHATWG Fetch specification.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RequestMode {
     /// Only allow same-origin requests
     SameOrigin,
     /// Use CORS for cross-origin requests (default)
+    #[default]
     Cors,
     /// Allow cross-origin requests without CORS
     NoCors,
@@ -75,38 +85,28 @@ pub enum RequestMode {
     Navigate,
 }
 
-impl Default for RequestMode {
-    fn default() -> Self {
-        Self::Cors
-    }
-}
-
 /// Credentials mode for requests.
 ///
 /// This enum controls whether credentials (cookies, authorization headers, etc.)
 /// are included in requests.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RequestCredentials {
     /// Never include credentials
     Omit,
     /// Include credentials for same-origin requests only (default)
+    #[default]
     SameOrigin,
     /// Always include credentials
     Include,
 }
 
-impl Default for RequestCredentials {
-    fn default() -> Self {
-        Self::SameOrigin
-    }
-}
-
 /// Cache mode for requests.
 ///
 /// This enum controls how the request interacts with the HTTP cache.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RequestCache {
     /// Use default cache behavior
+    #[default]
     Default,
     /// Don't use cache, don't store response
     NoStore,
@@ -120,31 +120,24 @@ pub enum RequestCache {
     OnlyIfCached,
 }
 
-impl Default for RequestCache {
-    fn default() -> Self {
-        Self::Default
-    }
-}
-
 /// Redirect mode for requests.
 ///
 /// This enum controls how redirects are handled.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum RequestRedirect {
     /// Follow redirects automatically (default)
+    #[default]
     Follow,
     /// Fail on redirects
     Error,
-    /// Return redirect response without following
+    /// Return an opaque-redirect response without following
+    ///
+    /// The returned [`Response`](crate::Response) has
+    /// [`ResponseType::OpaqueRedirect`](crate::ResponseType::OpaqueRedirect),
+    /// status 0, no headers and an empty body, as in the Fetch spec.
     Manual,
 }
 
-impl Default for RequestRedirect {
-    fn default() -> Self {
-        Self::Follow
-    }
-}
-
 /// Configuration for creating requests.
 ///
 /// `RequestInit` provides all the options that can be set when creating a new
@@ -165,13 +158,17 @@ impl Default for RequestRedirect {
 /// headers.set("Content-Type", "text/plain").unwrap();
 /// init.headers = Some(headers);
 /// ```
-#[derive(Debug, Clone, Default)]
+#[derive(Clone, Default)]
 pub struct RequestInit {
     /// HTTP method (GET, POST, PUT, etc.)
     pub method: Option<String>,
     /// Request headers
     pub headers: Option<Headers>,
     /// Request body
+    ///
+    /// `None` sends the request without a body and without body headers,
+    /// while `Some(ReadableStream::empty())` sends an explicitly empty body
+    /// announced with `Content-Length: 0`, as some servers require for `POST`.
     pub body: Option<ReadableStream>,
     /// CORS mode
     pub mode: Option<RequestMode>,
@@ -191,6 +188,60 @@ pub struct RequestInit {
     pub keepalive: Option<bool>,
     /// Abort signal for cancellation
     pub signal: Option<AbortSignal>,
+    /// Header name that receives the hex-encoded SHA-256 digest of the body
+    /// (e.g. `x-amz-content-sha256`)
+    ///
+    /// The digest is computed from the buffered body when the request is
+    /// created; bodiless requests get the digest of the empty string. Bodies
+    /// are always held in memory, so streaming sources would have to be
+    /// buffered before the digest can be produced.
+    pub content_sha256_header: Option<String>,
+    /// Maximum number of redirects to follow for this request
+    ///
+    /// Overrides the default limit of 20 used when redirects are followed.
+    /// Exceeding it fails the fetch with a [`NetworkError`](crate::NetworkError);
+    /// `Some(0)` rejects any redirect.
+    pub max_redirects: Option<usize>,
+    /// Maximum time to wait for the request to complete
+    ///
+    /// Covers connecting, sending, following redirects and reading the
+    /// response body. When it elapses the fetch fails with a
+    /// [`NetworkError`](crate::NetworkError). A client or process-wide timeout
+    /// still applies; whichever is shorter takes effect.
+    pub timeout: Option<Duration>,
+    /// Callback for `103 Early Hints` responses
+    ///
+    /// Called with the headers of every `103` the server sends before the
+    /// final response, typically `Link` headers naming resources to preload.
+    /// Other informational responses are ignored. The callback runs on the
+    /// connection task, so it should return quickly.
+    pub on_early_hints: Option<EarlyHintsCallback>,
+}
+
+impl fmt::Debug for RequestInit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestInit")
+            .field("method", &self.method)
+            .field("headers", &self.headers)
+            .field("body", &self.body)
+            .field("mode", &self.mode)
+            .field("credentials", &self.credentials)
+            .field("cache", &self.cache)
+            .field("redirect", &self.redirect)
+            .field("referrer", &self.referrer)
+            .field("referrer_policy", &self.referrer_policy)
+            .field("integrity", &self.integrity)
+            .field("keepalive", &self.keepalive)
+            .field("signal", &self.signal)
+            .field("content_sha256_header", &self.content_sha256_header)
+            .field("max_redirects", &self.max_redirects)
+            .field("timeout", &self.timeout)
+            .field(
+                "on_early_hints",
+                &self.on_early_hints.as_ref().map(|_| ".."),
+            )
+            .finish()
+    }
 }
 
 impl RequestInit {
@@ -211,6 +262,194 @@ impl RequestInit {
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Set the `Accept` header from an ordered list of preferred MIME types.
+    ///
+    /// The first type is sent without a quality value (implicitly `q=1`) and
+    /// each following type gets a weight 0.1 lower than the previous one, never
+    /// dropping below `q=0.1`. Any existing `Accept` header is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TypeError`] if the list is empty or contains something that
+    /// is not a `type/subtype` MIME type.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::RequestInit;
+    ///
+    /// let mut init = RequestInit::new();
+    /// init.accept_formats(&["application/json", "text/plain"]).unwrap();
+    ///
+    /// let headers = init.headers.unwrap();
+    /// assert_eq!(
+    ///     headers.get("accept").unwrap().unwrap(),
+    ///     "application/json, text/plain;q=0.9"
+    /// );
+    /// ```
+    pub fn accept_formats(&mut self, formats: &[&str]) -> Result<()> {
+        if formats.is_empty() {
+            return Err(FetchError::Type(TypeError::new(
+                "At least one format is required",
+            )));
+        }
+
+        let mut parts = Vec::with_capacity(formats.len());
+        for (index, format) in formats.iter().enumerate() {
+            let format = format.trim();
+            if !matches!(format.split_once('/'), Some((t, s)) if !t.is_empty() && !s.is_empty()) {
+                return Err(FetchError::Type(TypeError::new("Invalid MIME type")));
+            }
+
+            if index == 0 {
+                parts.push(format.to_string());
+            } else {
+                let q = (1.0 - 0.1 * index as f32).max(0.1);
+                parts.push(format!("{};q={}", format, format_qvalue(q)));
+            }
+        }
+
+        self.headers
+            .get_or_insert_with(Headers::new)
+            .set("accept", &parts.join(", "))
+    }
+
+    /// Set the `Accept-Language` header from language tags and quality values.
+    ///
+    /// Quality values are clamped to `0..=1` and written with at most three
+    /// decimals; a weight of `1` is left implicit. Languages are listed in the
+    /// order given. Any existing `Accept-Language` header is replaced.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TypeError`] if the list is empty, a tag is not a valid
+    /// language range (such as `en`, `en-US` or `*`), or a quality value is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::RequestInit;
+    ///
+    /// let mut init = RequestInit::new();
+    /// init.accept_language(&[("en-US", 1.0), ("fr", 0.8)]).unwrap();
+    ///
+    /// let headers = init.headers.unwrap();
+    /// assert_eq!(
+    ///     headers.get("accept-language").unwrap().unwrap(),
+    ///     "en-US, fr;q=0.8"
+    /// );
+    /// ```
+    pub fn accept_language(&mut self, languages: &[(&str, f32)]) -> Result<()> {
+        if languages.is_empty() {
+            return Err(FetchError::Type(TypeError::new(
+                "At least one language is required",
+            )));
+        }
+
+        let mut parts = Vec::with_capacity(languages.len());
+        for &(tag, q) in languages {
+            let tag = tag.trim();
+            if !is_language_range(tag) {
+                return Err(FetchError::Type(TypeError::new("Invalid language tag")));
+            }
+            if q.is_nan() {
+                return Err(FetchError::Type(TypeError::new("Invalid quality value")));
+            }
+
+            let q = format_qvalue(q);
+            if q == "1" {
+                parts.push(tag.to_string());
+            } else {
+                parts.push(format!("{};q={}", tag, q));
+            }
+        }
+
+        self.headers
+            .get_or_insert_with(Headers::new)
+            .set("accept-language", &parts.join(", "))
+    }
+
+    /// Set the `If-Range` header to make a range request conditional.
+    ///
+    /// `validator` is either a strong entity tag such as `"\"v1\""` or an
+    /// HTTP-date such as `"Wed, 21 Oct 2015 07:28:00 GMT"`, typically taken
+    /// from the `ETag` or `Last-Modified` of an earlier partial download.
+    ///
+    /// `If-Range` only has an effect together with a `Range` header, which
+    /// must be set separately. When the validator still matches, the server
+    /// answers `206 Partial Content` with the requested range. When the
+    /// resource has changed it ignores `Range` and sends the whole new
+    /// representation with `200 OK`, so a resumed download never splices
+    /// bytes from two different versions.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TypeError`] if `validator` is a weak entity tag (`W/"..."`),
+    /// which `If-Range` does not allow, or is neither an entity tag nor an
+    /// HTTP-date.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Headers, RequestInit};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set("Range", "bytes=1024-").unwrap();
+    ///
+    /// let mut init = RequestInit::new();
+    /// init.headers = Some(headers);
+    /// init.if_range("\"abc123\"").unwrap();
+    ///
+    /// let headers = init.headers.unwrap();
+    /// assert_eq!(headers.get("if-range").unwrap().unwrap(), "\"abc123\"");
+    /// assert_eq!(headers.get("range").unwrap().unwrap(), "bytes=1024-");
+    /// ```
+    pub fn if_range(&mut self, validator: &str) -> Result<()> {
+        let validator = validator.trim();
+        if validator.starts_with("W/") {
+            return Err(FetchError::Type(TypeError::new(
+                "If-Range requires a strong entity tag",
+            )));
+        }
+
+        let is_etag =
+            validator.len() >= 2 && validator.starts_with('"') && validator.ends_with('"');
+        if !is_etag && httpdate::parse_http_date(validator).is_err() {
+            return Err(FetchError::Type(TypeError::new(
+                "If-Range validator must be an entity tag or HTTP-date",
+            )));
+        }
+
+        self.headers
+            .get_or_insert_with(Headers::new)
+            .set("if-range", validator)
+    }
+}
+
+/// Whether `tag` is a language range as used in `Accept-Language` (RFC 4647).
+///
+/// That is `*`, or a primary subtag of 1-8 letters followed by any number of
+/// `-`-separated subtags of 1-8 letters or digits.
+fn is_language_range(tag: &str) -> bool {
+    if tag == "*" {
+        return true;
+    }
+    let mut subtags = tag.split('-');
+    let primary = subtags.next().unwrap_or("");
+    let valid_len = |s: &str| (1..=8).contains(&s.len());
+    valid_len(primary)
+        && primary.bytes().all(|b| b.is_ascii_alphabetic())
+        && subtags.all(|s| valid_len(s) && s.bytes().all(|b| b.is_ascii_alphanumeric()))
+}
+
+/// Format a quality value with at most three decimals and no trailing zeros.
+fn format_qvalue(q: f32) -> String {
+    let formatted = format!("{:.3}", q.clamp(0.0, 1.0));
+    formatted
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
 }
 
 /// An HTTP request following the WHATWG Fetch specification.
@@ -242,7 +481,7 @@ impl RequestInit {
 /// let request = Request::new("https://api.example.com/submit", Some(init)).unwrap();
 /// assert_eq!(request.method(), "POST");
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Request {
     /// Parsed URL for the request
     url: Url,
@@ -270,6 +509,41 @@ pub struct Request {
     keepalive: bool,
     /// Abort signal for cancellation
     signal: Option<AbortSignal>,
+    /// Per-request redirect limit
+    max_redirects: Option<usize>,
+    /// Per-request timeout
+    timeout: Option<Duration>,
+    /// Callback for `103 Early Hints` responses
+    on_early_hints: Option<EarlyHintsCallback>,
+    /// Header carrying the body digest, if one was attached
+    content_sha256_header: Option<String>,
+}
+
+impl fmt::Debug for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Request")
+            .field("url", &self.url)
+            .field("method", &self.method)
+            .field("headers", &self.headers)
+            .field("body", &self.body)
+            .field("mode", &self.mode)
+            .field("credentials", &self.credentials)
+            .field("cache", &self.cache)
+            .field("redirect", &self.redirect)
+            .field("referrer", &self.referrer)
+            .field("referrer_policy", &self.referrer_policy)
+            .field("integrity", &self.integrity)
+            .field("keepalive", &self.keepalive)
+            .field("signal", &self.signal)
+            .field("max_redirects", &self.max_redirects)
+            .field("timeout", &self.timeout)
+            .field(
+                "on_early_hints",
+                &self.on_early_hints.as_ref().map(|_| ".."),
+            )
+            .field("content_sha256_header", &self.content_sha256_header)
+            .finish()
+    }
 }
 
 impl Request {
@@ -292,6 +566,10 @@ impl Request {
     ///
     /// * [`TypeError`] - If the URL is invalid, method is invalid, or GET/HEAD requests have a body
     ///
+    /// IPv6 literal hosts such as `http://[::1]:8080/` are supported. IPv6
+    /// zone identifiers (`http://[fe80::1%eth0]/`) are not valid in WHATWG URLs
+    /// and are rejected with a [`TypeError`] saying so.
+    ///
     /// # Examples
     ///
     /// ```rust
@@ -320,7 +598,7 @@ impl Request {
     /// ```
     pub fn new(input: &str, init: Option<RequestInit>) -> Result<Self> {
         // Parse and validate URL
-        let url = Url::parse(input)?;
+        let url = validate_url(input)?;
         let init = init.unwrap_or_default();
 
         // Validate and normalize method
@@ -346,6 +624,15 @@ impl Request {
             }
         }
 
+        // Attach the body digest for stores that verify payload integrity
+        if let Some(ref header) = init.content_sha256_header {
+            let bytes = match init.body {
+                Some(ref body) => body.buffered_bytes()?,
+                None => bytes::Bytes::new(),
+            };
+            headers.set(header, &sha256_hex(&bytes))?;
+        }
+
         Ok(Self {
             url,
             method,
@@ -360,6 +647,10 @@ impl Request {
             integrity: init.integrity.unwrap_or_default(),
             keepalive: init.keepalive.unwrap_or(false),
             signal: init.signal,
+            max_redirects: init.max_redirects,
+            timeout: init.timeout,
+            on_early_hints: init.on_early_hints,
+            content_sha256_header: init.content_sha256_header,
         })
     }
 
@@ -404,6 +695,31 @@ impl Request {
         &self.method
     }
 
+    /// Get the request method as an [`http::Method`].
+    ///
+    /// Gives access to helpers such as [`is_safe()`](http::Method::is_safe)
+    /// and [`is_idempotent()`](http::Method::is_idempotent), for example when
+    /// deciding whether a request may be retried.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TypeError`] if the method is not a valid HTTP method. This
+    /// cannot happen for requests created with [`Request::new()`], which
+    /// validates the method.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::Request;
+    ///
+    /// let request = Request::new("https://example.com", None).unwrap();
+    /// assert!(request.http_method().unwrap().is_idempotent());
+    /// ```
+    pub fn http_method(&self) -> Result<http::Method> {
+        http::Method::from_bytes(self.method.as_bytes())
+            .map_err(|_| FetchError::Type(TypeError::new("Invalid method")))
+    }
+
     /// Get the request headers.
     ///
     /// # Returns
@@ -428,6 +744,35 @@ impl Request {
         &self.headers
     }
 
+    /// Get the `Content-Type` that will be sent with this request.
+    ///
+    /// Returns the `Content-Type` header if one is set, otherwise the type
+    /// inferred from the body (e.g. `application/json` for JSON bodies), or
+    /// `None` when neither applies.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Request, RequestInit, ReadableStream};
+    ///
+    /// let mut init = RequestInit::new();
+    /// init.method = Some("POST".to_string());
+    /// init.body = Some(ReadableStream::from_json(&serde_json::json!({"a": 1})));
+    ///
+    /// let request = Request::new("https://example.com", Some(init)).unwrap();
+    /// assert_eq!(request.effective_content_type().as_deref(), Some("application/json"));
+    /// ```
+    pub fn effective_content_type(&self) -> Option<String> {
+        match self.headers.get("content-type") {
+            Ok(Some(value)) => Some(value),
+            _ => self
+                .body
+                .as_ref()
+                .and_then(|body| body.get_content_type())
+                .map(str::to_string),
+        }
+    }
+
     /// Get the request body.
     ///
     /// # Returns
@@ -583,6 +928,41 @@ impl Request {
         self.keepalive
     }
 
+    /// Get the per-request redirect limit, if one was set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Request, RequestInit};
+    ///
+    /// let mut init = RequestInit::new();
+    /// init.max_redirects = Some(1);
+    ///
+    /// let request = Request::new("https://example.com", Some(init)).unwrap();
+    /// assert_eq!(request.max_redirects(), Some(1));
+    /// ```
+    pub fn max_redirects(&self) -> Option<usize> {
+        self.max_redirects
+    }
+
+    /// Get the per-request timeout, if one was set.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Request, RequestInit};
+    /// use std::time::Duration;
+    ///
+    /// let mut init = RequestInit::new();
+    /// init.timeout = Some(Duration::from_secs(5));
+    ///
+    /// let request = Request::new("https://example.com", Some(init)).unwrap();
+    /// assert_eq!(request.timeout(), Some(Duration::from_secs(5)));
+    /// ```
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
     /// Get the abort signal.
     ///
     /// # Returns
@@ -638,6 +1018,247 @@ impl Request {
         Ok(Clone::clone(self))
     }
 
+    /// Clone the request to send it to a different URL.
+    ///
+    /// The copy keeps the method, headers, body and every other setting of
+    /// this request, only pointing at `new_url` instead. This suits failover
+    /// across mirrors, where the same request is tried against one host after
+    /// another. Headers are copied as they are, including `Authorization`, so
+    /// only retarget to hosts trusted with them.
+    ///
+    /// # Errors
+    ///
+    /// * [`TypeError`] - If `new_url` is not a valid URL, or the request body
+    ///   has already been consumed
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{ReadableStream, Request, RequestInit};
+    ///
+    /// let mut init = RequestInit::new();
+    /// init.method = Some("POST".to_string());
+    /// init.body = Some(ReadableStream::from_text("data"));
+    ///
+    /// let request = Request::new("https://mirror1.example.com/upload", Some(init)).unwrap();
+    /// let retry = request.retarget("https://mirror2.example.com/upload").unwrap();
+    ///
+    /// assert_eq!(retry.url(), "https://mirror2.example.com/upload");
+    /// assert_eq!(retry.method(), "POST");
+    /// ```
+    pub fn retarget(&self, new_url: &str) -> Result<Self> {
+        if self.body_used() {
+            return Err(FetchError::Type(TypeError::new(
+                "Cannot retarget a request with a used body",
+            )));
+        }
+        let url = validate_url(new_url)?;
+        Ok(Self {
+            url,
+            ..Clone::clone(self)
+        })
+    }
+
+    /// Convert the request to a form that can be stored and sent later.
+    ///
+    /// The result holds the method, URL, headers and buffered body, and can be
+    /// serialized with serde, for example to keep requests in a durable queue
+    /// and replay them with [`from_serializable()`](Request::from_serializable).
+    /// Other settings, such as the abort signal, timeout and modes, are not
+    /// included.
+    ///
+    /// # Errors
+    ///
+    /// * [`TypeError`] - If the request body has already been consumed or
+    ///   cannot be buffered
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{ReadableStream, Request, RequestInit};
+    ///
+    /// let mut init = RequestInit::new();
+    /// init.method = Some("POST".to_string());
+    /// init.body = Some(ReadableStream::from_text("event"));
+    /// let request = Request::new("https://example.com/events", Some(init)).unwrap();
+    ///
+    /// let stored = serde_json::to_string(&request.to_serializable().unwrap()).unwrap();
+    ///
+    /// let restored = Request::from_serializable(serde_json::from_str(&stored).unwrap()).unwrap();
+    /// assert_eq!(restored.method(), "POST");
+    /// assert_eq!(restored.url(), "https://example.com/events");
+    /// ```
+    pub fn to_serializable(&self) -> Result<SerializableRequest> {
+        if self.body_used() {
+            return Err(FetchError::Type(TypeError::new(
+                "Cannot serialize a request with a used body",
+            )));
+        }
+        let body = match &self.body {
+            Some(body) => Some(body.buffered_bytes()?.to_vec()),
+            None => None,
+        };
+        Ok(SerializableRequest {
+            method: self.method.clone(),
+            url: self.url.to_string(),
+            headers: self
+                .headers
+                .entries()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+            ordered: self.headers.is_ordered(),
+            body,
+        })
+    }
+
+    /// Recreate a request stored with [`to_serializable()`](Request::to_serializable).
+    ///
+    /// The request is validated as by [`Request::new()`], since the stored
+    /// form may come from an untrusted or outdated source. Headers stored from
+    /// [`Headers::ordered()`] keep their insertion order. Settings not kept
+    /// in the stored form take their defaults.
+    ///
+    /// # Errors
+    ///
+    /// * [`TypeError`] - If the stored URL, method or a header is invalid, or
+    ///   a GET/HEAD request has a body
+    pub fn from_serializable(stored: SerializableRequest) -> Result<Self> {
+        let mut headers = if stored.ordered {
+            Headers::ordered()
+        } else {
+            Headers::new()
+        };
+        for (name, value) in &stored.headers {
+            headers.append(name, value)?;
+        }
+        let mut init = RequestInit::new();
+        init.method = Some(stored.method);
+        init.headers = Some(headers);
+        init.body = stored
+            .body
+            .map(|body| ReadableStream::from_bytes(bytes::Bytes::from(body)));
+        Self::new(&stored.url, Some(init))
+    }
+
+    /// Compute a stable key identifying this request for caching.
+    ///
+    /// The key combines the method, the URL as normalized by parsing (with
+    /// lowercase scheme and host, default port omitted and any fragment
+    /// dropped) and the request headers servers commonly list in `Vary`:
+    /// `Accept`, `Accept-Encoding` and `Accept-Language`. Header values have
+    /// whitespace around commas removed, so `gzip, br` and `gzip,br` give the
+    /// same key. Equivalent requests always produce the same key.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::Request;
+    ///
+    /// let a = Request::new("HTTPS://Example.com:443/a?b=1#top", None).unwrap();
+    /// let b = Request::new("https://example.com/a?b=1", None).unwrap();
+    /// assert_eq!(a.cache_key(), b.cache_key());
+    /// assert_eq!(a.cache_key(), "GET https://example.com/a?b=1");
+    /// ```
+    pub fn cache_key(&self) -> String {
+        let mut url = self.url.clone();
+        url.set_fragment(None);
+        let mut key = format!("{} {}", self.method, url);
+
+        for name in CACHE_KEY_HEADERS {
+            if let Ok(Some(value)) = self.headers.get(name) {
+                let value: Vec<&str> = value.split(',').map(str::trim).collect();
+                key.push_str(&format!("\n{}: {}", name, value.join(",")));
+            }
+        }
+        key
+    }
+
+    /// Render the request as a runnable `curl` command.
+    ///
+    /// The command includes the method, URL, all headers (sorted by name) and
+    /// the buffered body. Every value is single-quoted for POSIX shells, so the
+    /// output can be pasted straight into a terminal to reproduce the request.
+    /// Nothing is redacted; see [`to_curl_redacted()`] to hide sensitive headers.
+    ///
+    /// A UTF-8 body is passed with `--data-raw`. Other bodies cannot be given
+    /// as a shell argument without loss, so they are written with `printf`,
+    /// escaping bytes as octal, and piped to `curl --data-binary @-`.
+    ///
+    /// [`to_curl_redacted()`]: Request::to_curl_redacted
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::Request;
+    ///
+    /// let request = Request::new("https://example.com/users", None).unwrap();
+    /// assert_eq!(request.to_curl(), "curl -X 'GET' 'https://example.com/users'");
+    /// ```
+    pub fn to_curl(&self) -> String {
+        self.to_curl_redacted(&[])
+    }
+
+    /// Render the request as a `curl` command, redacting the given headers.
+    ///
+    /// Header names are matched case-insensitively and their values are
+    /// replaced with `[REDACTED]`. Otherwise identical to [`to_curl()`].
+    ///
+    /// [`to_curl()`]: Request::to_curl
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Request, RequestInit, Headers};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set("Authorization", "Bearer secret").unwrap();
+    ///
+    /// let mut init = RequestInit::new();
+    /// init.headers = Some(headers);
+    ///
+    /// let request = Request::new("https://example.com", Some(init)).unwrap();
+    /// let curl = request.to_curl_redacted(&["authorization"]);
+    /// assert!(curl.contains("-H 'authorization: [REDACTED]'"));
+    /// assert!(!curl.contains("secret"));
+    /// ```
+    pub fn to_curl_redacted(&self, redact: &[&str]) -> String {
+        let mut command = format!(
+            "curl -X {} {}",
+            shell_quote(&self.method),
+            shell_quote(self.url.as_str())
+        );
+
+        let mut entries: Vec<_> = self.headers.entries().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        for (name, value) in entries {
+            let value = if redact.iter().any(|r| r.eq_ignore_ascii_case(name)) {
+                "[REDACTED]"
+            } else {
+                value
+            };
+            command.push_str(" -H ");
+            command.push_str(&shell_quote(&format!("{}: {}", name, value)));
+        }
+
+        if let Some(Ok(bytes)) = self.body.as_ref().map(|b| b.buffered_bytes()) {
+            match std::str::from_utf8(&bytes) {
+                Ok(text) => {
+                    command.push_str(" --data-raw ");
+                    command.push_str(&shell_quote(text));
+                }
+                Err(_) => {
+                    command = format!(
+                        "printf {} | {} --data-binary @-",
+                        shell_quote(&printf_escape(&bytes)),
+                        command
+                    );
+                }
+            }
+        }
+
+        command
+    }
+
     /// Consume the request and return the body as bytes.
     ///
     /// # Examples
@@ -652,7 +1273,7 @@ impl Request {
     ///
     /// let request = Request::new("https://example.com", Some(init)).unwrap();
     /// let bytes = request.array_buffer().await.unwrap();
-    /// assert_eq!(bytes, b"Hello, World!");
+    /// assert_eq!(bytes, &b"Hello, World!"[..]);
     /// # });
     /// ```
     pub async fn array_buffer(self) -> Result<bytes::Bytes> {
@@ -753,6 +1374,16 @@ impl Request {
         }
     }
 
+    /// Get the early hints callback for use by the client.
+    pub(crate) fn on_early_hints(&self) -> Option<&EarlyHintsCallback> {
+        self.on_early_hints.as_ref()
+    }
+
+    /// Get the name of the body digest header for use by the client.
+    pub(crate) fn content_sha256_header(&self) -> Option<&str> {
+        self.content_sha256_header.as_deref()
+    }
+
     /// Get the internal URL object for use by the client.
     pub(crate) fn get_url(&self) -> &Url {
         &self.url
@@ -766,6 +1397,150 @@ impl Request {
     }
 }
 
+/// A [`Request`] in a storable form.
+///
+/// Created with [`Request::to_serializable()`] and turned back into a request
+/// with [`Request::from_serializable()`]. It implements serde's `Serialize`
+/// and `Deserialize`, so it can be written to disk or a message queue in any
+/// serde format.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SerializableRequest {
+    /// HTTP method
+    pub method: String,
+    /// Absolute request URL
+    pub url: String,
+    /// Header name-value pairs, with one pair per value of repeated headers
+    pub headers: Vec<(String, String)>,
+    /// Whether the headers keep insertion order, as with [`Headers::ordered()`]
+    #[serde(default)]
+    pub ordered: bool,
+    /// Request body, `None` when the request has none
+    pub body: Option<Vec<u8>>,
+}
+
+/// Request headers included in [`Request::cache_key()`].
+///
+/// These are the content-negotiation headers responses typically name in
+/// `Vary`, so requests differing in them may receive different responses.
+const CACHE_KEY_HEADERS: [&str; 3] = ["accept", "accept-encoding", "accept-language"];
+
+/// Compute the lowercase hex-encoded SHA-256 digest of `data`.
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Requests compare equal when they describe the same request configuration.
+///
+/// Two requests are equal if their URL, method, headers, mode, credentials
+/// mode, cache mode, redirect mode, redirect limit and timeout are equal and
+/// their bodies hold the same bytes (or both have no body). Header names are
+/// compared case-insensitively and header order does not matter. The abort
+/// signal, early hints callback, referrer, referrer policy, integrity
+/// metadata, keep-alive flag and whether the body has been used are not
+/// compared.
+///
+/// # Examples
+///
+/// ```rust
+/// use fetchttp::{ReadableStream, Request, RequestInit};
+///
+/// let build = || {
+///     let mut init = RequestInit::new();
+///     init.method = Some("post".to_string());
+///     init.body = Some(ReadableStream::from_text("payload"));
+///     Request::new("https://example.com/api", Some(init)).unwrap()
+/// };
+///
+/// assert_eq!(build(), build());
+/// assert_ne!(build(), Request::new("https://example.com/api", None).unwrap());
+/// ```
+impl PartialEq for Request {
+    fn eq(&self, other: &Self) -> bool {
+        let same_body = match (&self.body, &other.body) {
+            (None, None) => true,
+            (Some(a), Some(b)) => match (a.buffered_bytes(), b.buffered_bytes()) {
+                (Ok(a), Ok(b)) => a == b,
+                _ => false,
+            },
+            _ => false,
+        };
+
+        self.url == other.url
+            && self.method == other.method
+            && self.headers == other.headers
+            && self.mode == other.mode
+            && self.credentials == other.credentials
+            && self.cache == other.cache
+            && self.redirect == other.redirect
+            && self.max_redirects == other.max_redirects
+            && self.timeout == other.timeout
+            && same_body
+    }
+}
+
+/// Parse and validate a URL the same way [`Request::new`] does.
+///
+/// Useful for checking user input before building a request. The input must
+/// be an absolute URL; relative references are rejected.
+///
+/// # Errors
+///
+/// Returns a [`TypeError`] if the URL is relative or malformed, or uses an
+/// IPv6 zone identifier.
+///
+/// # Examples
+///
+/// ```rust
+/// use fetchttp::validate_url;
+///
+/// let url = validate_url("https://example.com/search?q=rust").unwrap();
+/// assert_eq!(url.host_str(), Some("example.com"));
+///
+/// assert!(validate_url("/relative/path").is_err());
+/// assert!(validate_url("http://exa mple.com").is_err());
+/// ```
+pub fn validate_url(input: &str) -> Result<Url> {
+    Url::parse(input).map_err(|e| match e {
+        url::ParseError::InvalidIpv6Address if has_ipv6_zone_id(input) => {
+            FetchError::Type(TypeError::new("IPv6 zone identifiers are not supported"))
+        }
+        e => e.into(),
+    })
+}
+
+/// Whether `input` has a bracketed IPv6 host carrying a zone identifier.
+fn has_ipv6_zone_id(input: &str) -> bool {
+    input
+        .split_once('[')
+        .and_then(|(_, rest)| rest.split_once(']'))
+        .is_some_and(|(host, _)| host.contains('%'))
+}
+
+/// Quote a string for safe use as a single POSIX shell word.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+/// Escape arbitrary bytes as a `printf` format that prints them unchanged.
+///
+/// Printable ASCII is kept, `%` and `\` are doubled and every other byte
+/// becomes a three-digit octal escape.
+fn printf_escape(bytes: &[u8]) -> String {
+    let mut format = String::with_capacity(bytes.len());
+    for &byte in bytes {
+        match byte {
+            b'%' => format.push_str("%%"),
+            b'\\' => format.push_str("\\\\"),
+            b' '..=b'~' => format.push(char::from(byte)),
+            _ => format.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    format
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -871,6 +1646,159 @@ mod tests {
         assert!(Request::new("", None).is_err());
     }
 
+    #[test]
+    fn test_extension_methods_preserved() {
+        for method in ["PURGE", "LINK", "UNLINK", "MKCOL", "PROPFIND", "purge"] {
+            let mut init = RequestInit::new();
+            init.method = Some(method.to_string());
+            let request = Request::new("https://example.com/", Some(init)).unwrap();
+            assert_eq!(request.method(), method);
+        }
+    }
+
+    #[test]
+    fn test_request_equality() {
+        let build = |body: &str| {
+            let mut headers = Headers::new();
+            headers.set("X-Trace", "abc").unwrap();
+            headers.set("Accept", "application/json").unwrap();
+            let mut init = RequestInit::new();
+            init.method = Some("POST".to_string());
+            init.headers = Some(headers);
+            init.body = Some(ReadableStream::from_text(body));
+            init.mode = Some(RequestMode::SameOrigin);
+            init.signal = Some(AbortSignal::new());
+            Request::new("https://example.com/items", Some(init)).unwrap()
+        };
+
+        assert_eq!(build("a"), build("a"));
+        assert_ne!(build("a"), build("b"));
+
+        // Buffered bytes are compared, not the body source kind
+        let mut init = RequestInit::new();
+        init.body = Some(ReadableStream::from_json(&serde_json::json!({"k": 1})));
+        init.method = Some("PUT".to_string());
+        let json = Request::new("https://example.com/", Some(init)).unwrap();
+
+        let mut init = RequestInit::new();
+        init.body = Some(ReadableStream::from_bytes(bytes::Bytes::from_static(
+            b"{\"k\":1}",
+        )));
+        init.method = Some("PUT".to_string());
+        let mut headers = Headers::new();
+        headers.set("content-type", "application/json").unwrap();
+        init.headers = Some(headers);
+        let raw = Request::new("https://example.com/", Some(init)).unwrap();
+        assert_eq!(json, raw);
+
+        let mut init = RequestInit::new();
+        init.cache = Some(RequestCache::NoStore);
+        assert_ne!(
+            Request::new("https://example.com/", Some(init)).unwrap(),
+            Request::new("https://example.com/", None).unwrap()
+        );
+
+        let mut init = RequestInit::new();
+        init.max_redirects = Some(0);
+        assert_ne!(
+            Request::new("https://example.com/", Some(init)).unwrap(),
+            Request::new("https://example.com/", None).unwrap()
+        );
+
+        let mut init = RequestInit::new();
+        init.timeout = Some(Duration::from_secs(5));
+        assert_ne!(
+            Request::new("https://example.com/", Some(init)).unwrap(),
+            Request::new("https://example.com/", None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_if_range() {
+        let mut init = RequestInit::new();
+        init.if_range("\"v1\"").unwrap();
+        assert_eq!(
+            init.headers
+                .as_ref()
+                .unwrap()
+                .get("if-range")
+                .unwrap()
+                .unwrap(),
+            "\"v1\""
+        );
+
+        init.if_range("Wed, 21 Oct 2015 07:28:00 GMT").unwrap();
+        assert_eq!(
+            init.headers
+                .as_ref()
+                .unwrap()
+                .get("if-range")
+                .unwrap()
+                .unwrap(),
+            "Wed, 21 Oct 2015 07:28:00 GMT"
+        );
+
+        assert!(init.if_range("W/\"v1\"").is_err());
+        assert!(init.if_range("v1").is_err());
+        assert!(init.if_range("\"").is_err());
+    }
+
+    #[test]
+    fn test_validate_url() {
+        let url = validate_url("https://example.com:8443/a?b=c#d").unwrap();
+        assert_eq!(url.port(), Some(8443));
+        assert_eq!(url.path(), "/a");
+        assert!(validate_url("http://[::1]/").is_ok());
+
+        // Relative inputs
+        for input in ["/path", "path/only", "//example.com/x", "?q=1"] {
+            assert!(
+                matches!(validate_url(input), Err(FetchError::Type(_))),
+                "{}",
+                input
+            );
+        }
+
+        // Malformed inputs
+        for input in [
+            "",
+            "http://",
+            "http://exa mple.com",
+            "https://[::1",
+            "http://a:99999/",
+        ] {
+            assert!(
+                matches!(validate_url(input), Err(FetchError::Type(_))),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_request_ipv6_literal() {
+        let request = Request::new("https://[::1]:8443/path", None).unwrap();
+        assert_eq!(request.url(), "https://[::1]:8443/path");
+        assert_eq!(request.get_url().port(), Some(8443));
+        assert_eq!(
+            request.get_url().host(),
+            Some(url::Host::Ipv6(std::net::Ipv6Addr::LOCALHOST))
+        );
+
+        let request = Request::new("http://[2001:db8::1]/", None).unwrap();
+        assert_eq!(request.get_url().port_or_known_default(), Some(80));
+    }
+
+    #[test]
+    fn test_request_ipv6_zone_id() {
+        for input in ["http://[fe80::1%eth0]:8080/", "http://[fe80::1%25eth0]/"] {
+            match Request::new(input, None) {
+                Err(FetchError::Type(e)) => assert!(e.to_string().contains("zone identifiers")),
+                other => panic!("expected zone identifier error, got ok={}", other.is_ok()),
+            }
+        }
+    }
+
     #[test]
     fn test_request_defaults() {
         let init = RequestInit::new();
@@ -886,6 +1814,9 @@ mod tests {
         assert!(init.integrity.is_none());
         assert!(init.keepalive.is_none());
         assert!(init.signal.is_none());
+        assert!(init.content_sha256_header.is_none());
+        assert!(init.max_redirects.is_none());
+        assert!(init.timeout.is_none());
     }
 
     #[test]
@@ -978,6 +1909,107 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_to_curl() {
+        let mut headers = Headers::new();
+        headers.set("X-Api-Key", "abc'123").unwrap();
+
+        let request = Request::new(
+            "https://example.com/items",
+            Some({
+                let mut init = RequestInit::new();
+                init.method = Some("POST".to_string());
+                init.headers = Some(headers);
+                init.body = Some(ReadableStream::from_text("hello world"));
+                init
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(
+            request.to_curl(),
+            "curl -X 'POST' 'https://example.com/items' \
+             -H 'content-type: text/plain;charset=UTF-8' \
+             -H 'x-api-key: abc'\\''123' \
+             --data-raw 'hello world'"
+        );
+
+        let redacted = request.to_curl_redacted(&["X-API-KEY"]);
+        assert!(redacted.contains("-H 'x-api-key: [REDACTED]'"));
+
+        // Bodies that are not UTF-8 are piped in byte for byte
+        let mut init = RequestInit::new();
+        init.method = Some("PUT".to_string());
+        init.body = Some(ReadableStream::from_bytes(bytes::Bytes::from_static(
+            b"\x00\xff%\\'a",
+        )));
+        let request = Request::new("https://example.com/blob", Some(init)).unwrap();
+        assert_eq!(
+            request.to_curl(),
+            "printf '\\000\\377%%\\\\'\\''a' | \
+             curl -X 'PUT' 'https://example.com/blob' --data-binary @-"
+        );
+    }
+
+    #[test]
+    fn test_accept_formats() {
+        let mut init = RequestInit::new();
+        init.accept_formats(&["application/json", "text/plain", "*/*"])
+            .unwrap();
+
+        let request = Request::new("https://example.com", Some(init)).unwrap();
+        assert_eq!(
+            request.headers().get("accept").unwrap().unwrap(),
+            "application/json, text/plain;q=0.9, */*;q=0.8"
+        );
+
+        let mut init = RequestInit::new();
+        assert!(init.accept_formats(&[]).is_err());
+        assert!(init.accept_formats(&["json"]).is_err());
+    }
+
+    #[test]
+    fn test_content_sha256_header() {
+        let request = Request::new(
+            "https://example.com/bucket/key",
+            Some({
+                let mut init = RequestInit::new();
+                init.method = Some("PUT".to_string());
+                init.body = Some(ReadableStream::from_text("hello"));
+                init.content_sha256_header = Some("x-amz-content-sha256".to_string());
+                init
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            request
+                .headers()
+                .get("x-amz-content-sha256")
+                .unwrap()
+                .unwrap(),
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+
+        // Bodiless requests carry the digest of the empty payload
+        let request = Request::new(
+            "https://example.com/bucket/key",
+            Some({
+                let mut init = RequestInit::new();
+                init.content_sha256_header = Some("x-amz-content-sha256".to_string());
+                init
+            }),
+        )
+        .unwrap();
+        assert_eq!(
+            request
+                .headers()
+                .get("x-amz-content-sha256")
+                .unwrap()
+                .unwrap(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
     #[test]
     fn test_request_clone() {
         let request = Request::new("https://example.com", None).unwrap();
@@ -986,4 +2018,211 @@ mod tests {
         assert_eq!(request.url(), cloned.url());
         assert_eq!(request.method(), cloned.method());
     }
+
+    #[tokio::test]
+    async fn test_request_retarget() {
+        let mut init = RequestInit::new();
+        init.method = Some("POST".to_string());
+        init.headers = Some(Headers::from(&[("X-Upload-Id", "42")]));
+        init.body = Some(ReadableStream::from_text("payload"));
+        let request = Request::new("https://primary.example.com/upload", Some(init)).unwrap();
+
+        let retargeted = request
+            .retarget("https://backup.example.com/upload")
+            .unwrap();
+        assert_eq!(retargeted.url(), "https://backup.example.com/upload");
+        assert_eq!(retargeted.method(), "POST");
+        assert_eq!(
+            retargeted.headers().get("x-upload-id").unwrap(),
+            Some("42".to_string())
+        );
+        assert_eq!(retargeted.text().await.unwrap(), "payload");
+
+        // The original is untouched and can still be sent
+        assert_eq!(request.url(), "https://primary.example.com/upload");
+        assert_eq!(request.text().await.unwrap(), "payload");
+
+        let request = Request::new("https://primary.example.com", None).unwrap();
+        assert!(request.retarget("not-a-url").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_serializable_round_trip() {
+        let mut headers = Headers::new();
+        headers
+            .set("Content-Type", "application/octet-stream")
+            .unwrap();
+        headers.append("X-Tag", "a").unwrap();
+        headers.append("X-Tag", "b").unwrap();
+        let mut init = RequestInit::new();
+        init.method = Some("PUT".to_string());
+        init.headers = Some(headers);
+        init.body = Some(ReadableStream::from_bytes(bytes::Bytes::from_static(
+            b"\x00\x01binary",
+        )));
+        let request = Request::new("https://example.com/items/1?v=2", Some(init)).unwrap();
+
+        let stored = request.to_serializable().unwrap();
+        let json = serde_json::to_string(&stored).unwrap();
+        let parsed: SerializableRequest = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, stored);
+
+        let restored = Request::from_serializable(parsed).unwrap();
+        assert_eq!(restored.method(), "PUT");
+        assert_eq!(restored.url(), "https://example.com/items/1?v=2");
+        assert_eq!(restored.headers(), request.headers());
+        assert_eq!(
+            restored.array_buffer().await.unwrap(),
+            bytes::Bytes::from_static(b"\x00\x01binary")
+        );
+
+        // A request without a body stays without one
+        let request = Request::new("https://example.com", None).unwrap();
+        let stored = request.to_serializable().unwrap();
+        assert_eq!(stored.body, None);
+        assert!(Request::from_serializable(stored).unwrap().body().is_none());
+
+        // Ordered headers keep their wire order on replay
+        let mut headers = Headers::ordered();
+        headers.append("X-Second", "2").unwrap();
+        headers.append("X-First", "1").unwrap();
+        let mut init = RequestInit::new();
+        init.headers = Some(headers);
+        let request = Request::new("https://example.com", Some(init)).unwrap();
+        let stored = request.to_serializable().unwrap();
+        assert!(stored.ordered);
+        let restored = Request::from_serializable(stored).unwrap();
+        assert!(restored.headers().is_ordered());
+        let names: Vec<_> = restored.headers().keys().collect();
+        assert_eq!(names, ["x-second", "x-first"]);
+
+        let invalid = SerializableRequest {
+            method: "GET".to_string(),
+            url: "https://example.com".to_string(),
+            headers: Vec::new(),
+            ordered: false,
+            body: Some(b"data".to_vec()),
+        };
+        assert!(Request::from_serializable(invalid).is_err());
+    }
+
+    #[test]
+    fn test_effective_content_type() {
+        let mut init = RequestInit::new();
+        init.method = Some("POST".to_string());
+        init.body = Some(ReadableStream::from_json(&serde_json::json!({"a": 1})));
+        let request = Request::new("https://example.com", Some(init)).unwrap();
+        assert_eq!(
+            request.effective_content_type().as_deref(),
+            Some("application/json")
+        );
+
+        let mut headers = Headers::new();
+        headers.set("Content-Type", "text/csv").unwrap();
+        let mut init = RequestInit::new();
+        init.method = Some("POST".to_string());
+        init.headers = Some(headers);
+        init.body = Some(ReadableStream::from_text("a,b"));
+        let request = Request::new("https://example.com", Some(init)).unwrap();
+        assert_eq!(
+            request.effective_content_type().as_deref(),
+            Some("text/csv")
+        );
+
+        let request = Request::new("https://example.com", None).unwrap();
+        assert_eq!(request.effective_content_type(), None);
+    }
+
+    #[test]
+    fn test_cache_key() {
+        fn request(url: &str, method: &str, headers: &[(&str, &str)]) -> Request {
+            let mut h = Headers::new();
+            for (name, value) in headers {
+                h.set(name, value).unwrap();
+            }
+            let mut init = RequestInit::new();
+            init.method = Some(method.to_string());
+            init.headers = Some(h);
+            Request::new(url, Some(init)).unwrap()
+        }
+
+        let a = request(
+            "https://Example.com:443/items?page=1#section",
+            "get",
+            &[("Accept-Encoding", "gzip, br"), ("X-Request-Id", "1")],
+        );
+        let b = request(
+            "https://example.com/items?page=1",
+            "GET",
+            &[("accept-encoding", "gzip,br"), ("X-Request-Id", "2")],
+        );
+        assert_eq!(a.cache_key(), b.cache_key());
+
+        let other_method = request("https://example.com/items?page=1", "HEAD", &[]);
+        let other_url = request("https://example.com/items?page=2", "GET", &[]);
+        let other_accept = request(
+            "https://example.com/items?page=1",
+            "GET",
+            &[("Accept-Encoding", "identity")],
+        );
+        for other in [other_method, other_url, other_accept] {
+            assert_ne!(a.cache_key(), other.cache_key());
+        }
+    }
+
+    #[test]
+    fn test_accept_language() {
+        let mut init = RequestInit::new();
+        init.accept_language(&[("en-US", 1.0), ("fr", 0.8), ("de-CH", 0.12345), ("*", 0.0)])
+            .unwrap();
+        assert_eq!(
+            init.headers
+                .unwrap()
+                .get("accept-language")
+                .unwrap()
+                .unwrap(),
+            "en-US, fr;q=0.8, de-CH;q=0.123, *;q=0"
+        );
+
+        // Out-of-range weights are clamped
+        let mut init = RequestInit::new();
+        init.accept_language(&[("en", 1.5), ("es-419", -1.0)])
+            .unwrap();
+        assert_eq!(
+            init.headers
+                .unwrap()
+                .get("accept-language")
+                .unwrap()
+                .unwrap(),
+            "en, es-419;q=0"
+        );
+
+        let mut init = RequestInit::new();
+        assert!(init.accept_language(&[]).is_err());
+        for tag in ["", "en_US", "toolonglang", "en-", "1en", "en US"] {
+            assert!(init.accept_language(&[(tag, 1.0)]).is_err(), "{}", tag);
+        }
+        assert!(init.accept_language(&[("en", f32::NAN)]).is_err());
+    }
+
+    #[test]
+    fn test_http_method() {
+        let request = Request::new("https://example.com", None).unwrap();
+        let method = request.http_method().unwrap();
+        assert_eq!(method, http::Method::GET);
+        assert!(method.is_idempotent());
+        assert!(method.is_safe());
+
+        let mut init = RequestInit::new();
+        init.method = Some("post".to_string());
+        let request = Request::new("https://example.com", Some(init)).unwrap();
+        let method = request.http_method().unwrap();
+        assert_eq!(method, http::Method::POST);
+        assert!(!method.is_idempotent());
+
+        let mut init = RequestInit::new();
+        init.method = Some("PURGE".to_string());
+        let request = Request::new("https://example.com", Some(init)).unwrap();
+        assert_eq!(request.http_method().unwrap().as_str(), "PURGE");
+    }
 }
@@ -21,7 +21,7 @@
 //! ```rust
 //! use fetchttp::*;
 //!
-//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! # async fn example() -> std::result::Result<(), Box<dyn std::error::Error>> {
 //! let response = fetch("https://httpbin.org/json", None).await?;
 //!
 //! if response.ok() {
@@ -61,16 +61,32 @@
 //! ).unwrap();
 //! ```
 
-use crate::error::{FetchError, Result, TypeError};
+use crate::error::{FetchError, NetworkError, Result, TypeError};
 use crate::{Headers, ReadableStream};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+use url::Url;
+
+/// Hop-by-hop headers, which describe a single connection and are never cached.
+const HOP_BY_HOP_HEADERS: [&str; 8] = [
+    "connection",
+    "keep-alive",
+    "proxy-authenticate",
+    "proxy-authorization",
+    "te",
+    "trailer",
+    "transfer-encoding",
+    "upgrade",
+];
 
 /// Response type classification.
 ///
 /// This enum classifies responses according to the WHATWG Fetch specification,
 /// providing information about the response's origin and processing.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ResponseType {
     /// Basic response from same origin
+    #[default]
     Basic,
     /// CORS response from cross-origin request
     Cors,
@@ -84,9 +100,116 @@ pub enum ResponseType {
     OpaqueRedirect,
 }
 
-impl Default for ResponseType {
-    fn default() -> Self {
-        Self::Basic
+/// How a response body was delimited on the wire.
+///
+/// Returned by [`Response::transfer_framing()`]. A chunked `Transfer-Encoding`
+/// takes precedence over `Content-Length`, as HTTP/1.1 requires; a message
+/// carrying both is a classic request-smuggling vector.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferFraming {
+    /// The body length was given by `Content-Length`
+    ContentLength(u64),
+    /// The body was sent with chunked transfer encoding
+    Chunked,
+    /// Neither applies, for example a body delimited by closing the connection
+    Unknown,
+}
+
+impl TransferFraming {
+    /// Determine the framing from `Transfer-Encoding` and `Content-Length` values.
+    pub(crate) fn from_header_values(
+        transfer_encoding: Option<&str>,
+        content_length: Option<&str>,
+    ) -> Self {
+        if let Some(codings) = transfer_encoding {
+            // Only a final `chunked` coding delimits the body
+            let last = codings.rsplit(',').next().unwrap_or("").trim();
+            return if last.eq_ignore_ascii_case("chunked") {
+                Self::Chunked
+            } else {
+                Self::Unknown
+            };
+        }
+        content_length
+            .and_then(|value| value.split(',').next())
+            .and_then(|value| value.trim().parse().ok())
+            .map_or(Self::Unknown, Self::ContentLength)
+    }
+
+    /// Determine the framing of a raw HTTP response from its headers.
+    pub(crate) fn from_http_headers(headers: &http::HeaderMap) -> Self {
+        let transfer_encoding = headers
+            .get_all(http::header::TRANSFER_ENCODING)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .collect::<Vec<_>>();
+        let transfer_encoding =
+            (!transfer_encoding.is_empty()).then(|| transfer_encoding.join(", "));
+        let content_length = headers
+            .get(http::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok());
+        Self::from_header_values(transfer_encoding.as_deref(), content_length)
+    }
+}
+
+/// A parsed `Strict-Transport-Security` (HSTS) policy.
+///
+/// Returned by [`Response::hsts()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hsts {
+    /// How long the host should only be contacted over HTTPS; zero tells
+    /// clients to forget the policy
+    pub max_age: Duration,
+    /// Whether the policy also covers all subdomains
+    pub include_subdomains: bool,
+    /// Whether the host asks to be included in browser preload lists
+    pub preload: bool,
+}
+
+impl Hsts {
+    /// Parse a `Strict-Transport-Security` header value as RFC 6797 describes.
+    ///
+    /// Only the first policy of a combined value is used, and a policy
+    /// without a valid `max-age` or with a repeated directive is invalid.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let policy = value.split(',').next().unwrap_or_default();
+        let mut max_age = None;
+        let mut include_subdomains = false;
+        let mut preload = false;
+        let mut seen = Vec::new();
+
+        for directive in policy.split(';') {
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(unquote(value.trim()))),
+                None => (directive.trim(), None),
+            };
+            if name.is_empty() {
+                continue;
+            }
+            let name = name.to_ascii_lowercase();
+            if seen.contains(&name) {
+                return None;
+            }
+            match name.as_str() {
+                "max-age" => {
+                    let seconds = value?;
+                    if seconds.is_empty() || !seconds.bytes().all(|b| b.is_ascii_digit()) {
+                        return None;
+                    }
+                    max_age = Some(Duration::from_secs(seconds.parse().unwrap_or(u64::MAX)));
+                }
+                "includesubdomains" => include_subdomains = true,
+                "preload" => preload = true,
+                _ => {}
+            }
+            seen.push(name);
+        }
+
+        Some(Self {
+            max_age: max_age?,
+            include_subdomains,
+            preload,
+        })
     }
 }
 
@@ -188,6 +311,14 @@ pub struct Response {
     headers: Headers,
     /// Response body (optional)
     body: Option<ReadableStream>,
+    /// Bytes left under the client's `max_response_bytes`, if one was set
+    body_limit_remaining: Option<usize>,
+    /// DNS lookup time of the connection the response arrived on
+    dns_time: Option<Duration>,
+    /// HTTP version of the response
+    version: http::Version,
+    /// Extensions attached to the underlying HTTP response
+    extensions: http::Extensions,
 }
 
 impl Response {
@@ -263,6 +394,10 @@ impl Response {
             status_text,
             headers: init.headers.unwrap_or_default(),
             body,
+            body_limit_remaining: None,
+            dns_time: None,
+            version: http::Version::HTTP_11,
+            extensions: http::Extensions::new(),
         })
     }
 
@@ -294,9 +429,45 @@ impl Response {
             status_text: String::new(),
             headers: Headers::new(),
             body: None,
+            body_limit_remaining: None,
+            dns_time: None,
+            version: http::Version::HTTP_11,
+            extensions: http::Extensions::new(),
         }
     }
 
+    /// Create an error response carrying a status code and body.
+    ///
+    /// Unlike [`error()`], which always produces a bodyless status-0 response,
+    /// this keeps the given status and body while still reporting
+    /// [`ResponseType::Error`]. It is mainly useful for building error fixtures
+    /// in tests.
+    ///
+    /// [`error()`]: Response::error
+    ///
+    /// # Errors
+    ///
+    /// * [`TypeError`] - If the status code is invalid (not 200-599)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Response, ResponseType, ReadableStream};
+    ///
+    /// let response = Response::error_with(503, Some(ReadableStream::from_text("down"))).unwrap();
+    /// assert_eq!(response.status(), 503);
+    /// assert_eq!(response.status_text(), "Service Unavailable");
+    /// assert_eq!(response.response_type(), ResponseType::Error);
+    /// ```
+    pub fn error_with(status: u16, body: Option<ReadableStream>) -> Result<Self> {
+        let mut init = ResponseInit::new();
+        init.status = Some(status);
+
+        let mut response = Self::new(body, Some(init))?;
+        response.response_type = ResponseType::Error;
+        Ok(response)
+    }
+
     /// Create a redirect response.
     ///
     /// Redirect responses have a status code in the 3xx range and include
@@ -354,9 +525,55 @@ impl Response {
             status_text: Self::default_status_text(status),
             headers,
             body: None,
+            body_limit_remaining: None,
+            dns_time: None,
+            version: http::Version::HTTP_11,
+            extensions: http::Extensions::new(),
         })
     }
 
+    /// Create a JSON response from a serializable value.
+    ///
+    /// Serializes `value` as the body and sets `Content-Type:
+    /// application/json`. This is a shorthand for building mock responses in
+    /// tests.
+    ///
+    /// # Errors
+    ///
+    /// * [`TypeError`] - If `value` cannot be serialized or the status code is
+    ///   invalid (not 200-599)
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::Response;
+    /// use serde_json::json;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let response = Response::json_response(&json!({"id": 1}), 201).unwrap();
+    /// assert_eq!(response.status(), 201);
+    /// assert_eq!(
+    ///     response.headers().get("content-type").unwrap().unwrap(),
+    ///     "application/json"
+    /// );
+    ///
+    /// let data: serde_json::Value = response.json().await.unwrap();
+    /// assert_eq!(data["id"], 1);
+    /// # });
+    /// ```
+    pub fn json_response<T: serde::Serialize>(value: &T, status: u16) -> Result<Self> {
+        let value = serde_json::to_value(value)
+            .map_err(|_| FetchError::Type(TypeError::new("Failed to serialize JSON")))?;
+
+        let mut headers = Headers::new();
+        headers.set("content-type", "application/json")?;
+
+        let mut init = ResponseInit::new();
+        init.status = Some(status);
+        init.headers = Some(headers);
+        Self::new(Some(ReadableStream::from_json(&value)), Some(init))
+    }
+
     /// Get the response type.
     ///
     /// # Examples
@@ -404,6 +621,25 @@ impl Response {
         self.redirected
     }
 
+    /// Check if the response was delivered by HTTP/2 server push.
+    ///
+    /// This is currently always `false`: the HTTP/2 client disables server
+    /// push, so every response answers a request this library sent. The
+    /// accessor exists so code can handle pushed responses once they are
+    /// supported, without an API change.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::Response;
+    ///
+    /// let response = Response::new(None, None).unwrap();
+    /// assert!(!response.was_pushed());
+    /// ```
+    pub fn was_pushed(&self) -> bool {
+        false
+    }
+
     /// Get the HTTP status code.
     ///
     /// # Examples
@@ -493,6 +729,235 @@ impl Response {
         &self.headers
     }
 
+    /// Get an owned copy of the response headers.
+    ///
+    /// Useful for moving headers into a new [`RequestInit`](crate::RequestInit),
+    /// for example to forward an `X-Request-Id` to a follow-up request.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Headers, RequestInit, Response, ResponseInit};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set("X-Request-Id", "abc123").unwrap();
+    ///
+    /// let mut init = ResponseInit::new();
+    /// init.headers = Some(headers);
+    /// let response = Response::new(None, Some(init)).unwrap();
+    ///
+    /// let mut next = RequestInit::new();
+    /// next.headers = Some(response.headers_cloned());
+    /// ```
+    pub fn headers_cloned(&self) -> Headers {
+        self.headers.clone()
+    }
+
+    /// Get the filename suggested by the `Content-Disposition` header.
+    ///
+    /// Both the plain `filename=` parameter and the RFC 5987 `filename*=`
+    /// form (e.g. `filename*=UTF-8''na%C3%AFve.txt`) are understood; the
+    /// extended form wins when both are present. Any directory components are
+    /// stripped so the result is safe to use as a file name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Headers, Response, ResponseInit};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers
+    ///     .set("Content-Disposition", "attachment; filename=\"report.pdf\"")
+    ///     .unwrap();
+    ///
+    /// let mut init = ResponseInit::new();
+    /// init.headers = Some(headers);
+    ///
+    /// let response = Response::new(None, Some(init)).unwrap();
+    /// assert_eq!(response.suggested_filename().unwrap(), "report.pdf");
+    /// ```
+    pub fn suggested_filename(&self) -> Option<String> {
+        let value = self.headers.get("content-disposition").ok()??;
+        let mut plain = None;
+        let mut extended = None;
+
+        for param in split_header_params(&value).into_iter().skip(1) {
+            let Some((name, raw)) = param.split_once('=') else {
+                continue;
+            };
+            let name = name.trim().to_ascii_lowercase();
+            let raw = raw.trim();
+            match name.as_str() {
+                "filename*" => extended = decode_ext_value(raw),
+                "filename" => plain = Some(unquote(raw)),
+                _ => {}
+            }
+        }
+
+        let filename = extended.or(plain)?;
+        let filename = filename
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        (!filename.is_empty() && filename != "." && filename != "..").then_some(filename)
+    }
+
+    /// Parse the `Link` header into a map from relation type to target URL.
+    ///
+    /// Relative targets are resolved against [`url()`](Self::url). Relation
+    /// types are lowercased, a link with several space-separated relations is
+    /// listed under each of them, and when a relation appears more than once
+    /// the first link wins. Links whose target cannot be resolved are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Headers, Response, ResponseInit};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers
+    ///     .set(
+    ///         "Link",
+    ///         "<https://api.example.com/items?page=3>; rel=\"next\", \
+    ///          <https://api.example.com/items?page=1>; rel=\"prev\"",
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let mut init = ResponseInit::new();
+    /// init.headers = Some(headers);
+    ///
+    /// let response = Response::new(None, Some(init)).unwrap();
+    /// let links = response.links();
+    /// assert_eq!(links["prev"].as_str(), "https://api.example.com/items?page=1");
+    /// ```
+    pub fn links(&self) -> HashMap<String, Url> {
+        let mut links = HashMap::new();
+        let Ok(Some(value)) = self.headers.get("link") else {
+            return links;
+        };
+        let base = Url::parse(&self.url).ok();
+
+        for link in split_link_values(&value) {
+            let mut params = split_header_params(link).into_iter();
+            let Some(target) = params
+                .next()
+                .and_then(|target| target.strip_prefix('<'))
+                .and_then(|target| target.strip_suffix('>'))
+            else {
+                continue;
+            };
+            let target = match &base {
+                Some(base) => base.join(target.trim()),
+                None => Url::parse(target.trim()),
+            };
+            let Ok(target) = target else {
+                continue;
+            };
+
+            let rels = params.find_map(|param| {
+                let (name, raw) = param.split_once('=')?;
+                name.trim()
+                    .eq_ignore_ascii_case("rel")
+                    .then(|| unquote(raw.trim()))
+            });
+            for rel in rels.iter().flat_map(|rels| rels.split_ascii_whitespace()) {
+                links
+                    .entry(rel.to_ascii_lowercase())
+                    .or_insert_with(|| target.clone());
+            }
+        }
+        links
+    }
+
+    /// Get the URL of the next page from the `Link` header, if any.
+    ///
+    /// Shorthand for looking up the `next` relation in [`links()`](Self::links),
+    /// which makes pagination loops straightforward.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Headers, Response, ResponseInit};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers
+    ///     .set("Link", "<https://api.example.com/items?page=2>; rel=\"next\"")
+    ///     .unwrap();
+    ///
+    /// let mut init = ResponseInit::new();
+    /// init.headers = Some(headers);
+    ///
+    /// let response = Response::new(None, Some(init)).unwrap();
+    /// assert_eq!(
+    ///     response.next_page().unwrap().as_str(),
+    ///     "https://api.example.com/items?page=2"
+    /// );
+    /// ```
+    pub fn next_page(&self) -> Option<Url> {
+        self.links().remove("next")
+    }
+
+    /// Update a cached response with the headers of a `304 Not Modified`.
+    ///
+    /// Every end-to-end header of `from` replaces the header of the same name
+    /// on `self`, as HTTP caches do when a stored response is revalidated.
+    /// Hop-by-hop headers (`Connection`, `Keep-Alive`, `Transfer-Encoding`,
+    /// etc., plus any listed in `from`'s `Connection` header) are skipped, as
+    /// is `Content-Length`, which describes the stored body rather than the
+    /// empty 304. The status and body of `self` are left untouched.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Headers, ReadableStream, Response, ResponseInit};
+    ///
+    /// let mut cached = Response::new(Some(ReadableStream::from_text("cached")), None).unwrap();
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set("ETag", "\"v2\"").unwrap();
+    /// let mut init = ResponseInit::new();
+    /// init.status = Some(304);
+    /// init.headers = Some(headers);
+    /// let not_modified = Response::new(None, Some(init)).unwrap();
+    ///
+    /// cached.merge_validated_headers(&not_modified);
+    /// assert_eq!(cached.headers().get("etag").unwrap().unwrap(), "\"v2\"");
+    /// assert_eq!(cached.status(), 200);
+    /// ```
+    pub fn merge_validated_headers(&mut self, from: &Response) {
+        let connection_listed: Vec<String> = from
+            .headers
+            .get("connection")
+            .ok()
+            .flatten()
+            .map(|value| {
+                value
+                    .split(',')
+                    .map(|name| name.trim().to_ascii_lowercase())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut replaced: Vec<&str> = Vec::new();
+        for (name, value) in from.headers.entries() {
+            if HOP_BY_HOP_HEADERS.contains(&name)
+                || name == "content-length"
+                || connection_listed.iter().any(|listed| listed == name)
+            {
+                continue;
+            }
+            // Names and values already passed validation on `from`
+            if replaced.contains(&name) {
+                let _ = self.headers.append(name, value);
+            } else {
+                let _ = self.headers.set(name, value);
+                replaced.push(name);
+            }
+        }
+    }
+
     /// Get the response body.
     ///
     /// # Examples
@@ -535,6 +1000,231 @@ impl Response {
         self.body.as_ref().is_some_and(|b| b.is_used())
     }
 
+    /// Estimate the bytes of heap memory held by this response.
+    ///
+    /// The estimate adds up the status text, URL, header names and values,
+    /// and the buffered body, which usually dominates. Allocator overhead and
+    /// small fixed-size fields are not counted, so this is an approximation
+    /// meant for enforcing memory budgets across many responses rather than
+    /// an exact measure. A consumed body no longer counts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{ReadableStream, Response};
+    ///
+    /// let small = Response::new(Some(ReadableStream::from_text("hi")), None).unwrap();
+    /// let large = Response::new(Some(ReadableStream::from_text(&"x".repeat(4096))), None).unwrap();
+    /// assert!(large.approx_heap_size() > small.approx_heap_size() + 4000);
+    /// ```
+    pub fn approx_heap_size(&self) -> usize {
+        let body = self
+            .body
+            .as_ref()
+            .filter(|body| !body.is_used())
+            .map_or(0, ReadableStream::approx_heap_size);
+        self.status_text.capacity() + self.url.capacity() + self.headers.approx_heap_size() + body
+    }
+
+    /// Get how many more body bytes the client's size limit would have allowed.
+    ///
+    /// Returns `None` unless the response was fetched through a
+    /// [`FetchClient`] configured with [`max_response_bytes`]. The limit is
+    /// enforced while the body is received and again while it is decoded, and
+    /// a body passing it fails the fetch. This value is only computed after
+    /// that, once the whole body is buffered: it is the limit minus the size
+    /// of the decoded body. It does not report progress while a body is read.
+    ///
+    /// [`FetchClient`]: crate::FetchClient
+    /// [`max_response_bytes`]: crate::FetchClientBuilder::max_response_bytes
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::Response;
+    ///
+    /// let response = Response::new(None, None).unwrap();
+    /// assert!(response.body_limit_remaining().is_none());
+    /// ```
+    pub fn body_limit_remaining(&self) -> Option<usize> {
+        self.body_limit_remaining
+    }
+
+    /// Get how long the DNS lookup took for the connection that carried this response.
+    ///
+    /// This separates slow name resolution from slow connects in performance
+    /// audits. It is `None` when no lookup happened, for example when the URL
+    /// host is an IP address, and for responses not produced by a fetch. A
+    /// response received on a reused pooled connection reports the lookup that
+    /// opened that connection.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use fetchttp::*;
+    ///
+    /// # async fn example() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    /// let response = fetch("https://example.com", None).await?;
+    /// if let Some(time) = response.dns_time() {
+    ///     println!("DNS lookup took {:?}", time);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dns_time(&self) -> Option<Duration> {
+        self.dns_time
+    }
+
+    /// Get the HTTP version the response was received with.
+    ///
+    /// Responses not produced by a fetch report HTTP/1.1.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::Response;
+    ///
+    /// let response = Response::new(None, None).unwrap();
+    /// assert_eq!(response.version(), http::Version::HTTP_11);
+    /// ```
+    pub fn version(&self) -> http::Version {
+        self.version
+    }
+
+    /// Get the extensions attached to the underlying HTTP response.
+    ///
+    /// These hold data injected by the connection layer, such as hyper-util's
+    /// `HttpInfo` with the local and remote socket addresses. Responses not
+    /// produced by a fetch have no extensions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use fetchttp::*;
+    /// use hyper_util::client::legacy::connect::HttpInfo;
+    ///
+    /// # async fn example() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    /// let response = fetch("https://example.com", None).await?;
+    /// if let Some(info) = response.extensions().get::<HttpInfo>() {
+    ///     println!("Connected to {}", info.remote_addr());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn extensions(&self) -> &http::Extensions {
+        &self.extensions
+    }
+
+    /// Get how the response body was framed: by length or in chunks.
+    ///
+    /// For fetched responses this reflects the headers as received, before
+    /// any decompression removed `Content-Length`. Other responses are judged
+    /// by their current [`headers()`](Response::headers). Useful when
+    /// debugging proxies or looking for framing ambiguities.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Headers, Response, ResponseInit, TransferFraming};
+    ///
+    /// let mut init = ResponseInit::new();
+    /// init.headers = Some(Headers::from(&[("Content-Length", "42")]));
+    /// let response = Response::new(None, Some(init)).unwrap();
+    /// assert_eq!(response.transfer_framing(), TransferFraming::ContentLength(42));
+    ///
+    /// let response = Response::new(None, None).unwrap();
+    /// assert_eq!(response.transfer_framing(), TransferFraming::Unknown);
+    /// ```
+    pub fn transfer_framing(&self) -> TransferFraming {
+        if let Some(framing) = self.extensions.get::<TransferFraming>() {
+            return *framing;
+        }
+        let transfer_encoding = self.headers.get("transfer-encoding").ok().flatten();
+        let content_length = self.headers.get("content-length").ok().flatten();
+        TransferFraming::from_header_values(transfer_encoding.as_deref(), content_length.as_deref())
+    }
+
+    /// Parse the `Strict-Transport-Security` header.
+    ///
+    /// Returns `None` when the header is missing or invalid, for instance
+    /// without a `max-age`. Directive names are case-insensitive and unknown
+    /// directives are ignored. Browsers only honor the header on HTTPS
+    /// responses; this method parses it regardless of the scheme.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Headers, Response, ResponseInit};
+    /// use std::time::Duration;
+    ///
+    /// let mut init = ResponseInit::new();
+    /// init.headers = Some(Headers::from(&[(
+    ///     "Strict-Transport-Security",
+    ///     "max-age=31536000; includeSubDomains",
+    /// )]));
+    /// let response = Response::new(None, Some(init)).unwrap();
+    ///
+    /// let hsts = response.hsts().unwrap();
+    /// assert_eq!(hsts.max_age, Duration::from_secs(31536000));
+    /// assert!(hsts.include_subdomains);
+    /// assert!(!hsts.preload);
+    /// ```
+    pub fn hsts(&self) -> Option<Hsts> {
+        let value = self.headers.get("strict-transport-security").ok()??;
+        Hsts::parse(&value)
+    }
+
+    /// Parse the `Age` header: how long the response has been held in caches.
+    ///
+    /// The value must be a whole number of seconds. Following RFC 9111, ages
+    /// too large to represent are capped at 2^31 seconds. Returns `None` when
+    /// the header is missing or malformed, including when it was received
+    /// more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Headers, Response, ResponseInit};
+    /// use std::time::Duration;
+    ///
+    /// let mut init = ResponseInit::new();
+    /// init.headers = Some(Headers::from(&[("Age", "120")]));
+    /// let response = Response::new(None, Some(init)).unwrap();
+    /// assert_eq!(response.age(), Some(Duration::from_secs(120)));
+    /// ```
+    pub fn age(&self) -> Option<Duration> {
+        const MAX_AGE: u64 = 1 << 31;
+
+        let value = self.headers.get("age").ok()??;
+        let value = value.trim();
+        if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let seconds = value.parse::<u64>().unwrap_or(MAX_AGE).min(MAX_AGE);
+        Some(Duration::from_secs(seconds))
+    }
+
+    /// Parse the `Date` header: when the origin server generated the response.
+    ///
+    /// All three HTTP-date formats are accepted (IMF-fixdate, RFC 850 and
+    /// asctime). Returns `None` when the header is missing or malformed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Headers, Response, ResponseInit};
+    /// use std::time::{Duration, UNIX_EPOCH};
+    ///
+    /// let mut init = ResponseInit::new();
+    /// init.headers = Some(Headers::from(&[("Date", "Sun, 06 Nov 1994 08:49:37 GMT")]));
+    /// let response = Response::new(None, Some(init)).unwrap();
+    /// assert_eq!(response.date(), Some(UNIX_EPOCH + Duration::from_secs(784111777)));
+    /// ```
+    pub fn date(&self) -> Option<SystemTime> {
+        let value = self.headers.get("date").ok()??;
+        httpdate::parse_http_date(value.trim()).ok()
+    }
+
     /// Clone the response (WHATWG Fetch API method).
     ///
     /// This method follows the WHATWG Fetch specification for cloning responses.
@@ -582,7 +1272,7 @@ impl Response {
     /// ).unwrap();
     ///
     /// let bytes = response.array_buffer().await.unwrap();
-    /// assert_eq!(bytes, b"Hello, World!");
+    /// assert_eq!(bytes, &b"Hello, World!"[..]);
     /// # });
     /// ```
     pub async fn array_buffer(self) -> Result<bytes::Bytes> {
@@ -592,7 +1282,115 @@ impl Response {
         }
     }
 
-    /// Consume the response and return the body as a blob (bytes).
+    /// Consume the response and append the body to `buf`.
+    ///
+    /// Returns the number of bytes appended. Existing contents of `buf` are
+    /// kept.
+    ///
+    /// This does not avoid an allocation. [`fetch`](crate::fetch) buffers the
+    /// whole body before the response exists, so the body is already in its
+    /// own allocation and this copies it into `buf`. It suits code that
+    /// collects bodies into a buffer it owns; [`array_buffer()`] returns the
+    /// body without copying it.
+    ///
+    /// [`array_buffer()`]: Response::array_buffer
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Response, ReadableStream};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut buf = Vec::with_capacity(1024);
+    ///
+    /// let response = Response::new(Some(ReadableStream::from_text("first")), None).unwrap();
+    /// assert_eq!(response.read_into(&mut buf).await.unwrap(), 5);
+    /// assert_eq!(buf, b"first");
+    /// # });
+    /// ```
+    pub async fn read_into(self, buf: &mut Vec<u8>) -> Result<usize> {
+        let bytes = self.array_buffer().await?;
+        buf.extend_from_slice(&bytes);
+        Ok(bytes.len())
+    }
+
+    /// Consume the response and base64-decode its body.
+    ///
+    /// For APIs that send binary payloads base64-encoded, for example with
+    /// `Content-Transfer-Encoding: base64`. The body is decoded with the
+    /// standard alphabet; ASCII whitespace, such as the line breaks in
+    /// MIME-wrapped data, is ignored. Invalid base64 is a [`TypeError`].
+    ///
+    /// [`TypeError`]: crate::TypeError
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Response, ReadableStream};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let response = Response::new(Some(ReadableStream::from_text("aGVsbG8=")), None).unwrap();
+    /// assert_eq!(response.base64_decode().await.unwrap(), &b"hello"[..]);
+    /// # });
+    /// ```
+    pub async fn base64_decode(self) -> Result<bytes::Bytes> {
+        use base64::Engine;
+
+        let body = self.array_buffer().await?;
+        let encoded: Vec<u8> = body
+            .iter()
+            .copied()
+            .filter(|byte| !byte.is_ascii_whitespace())
+            .collect();
+        base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map(bytes::Bytes::from)
+            .map_err(|e| FetchError::Type(TypeError::new(&format!("Invalid base64 body: {}", e))))
+    }
+
+    /// Check that the response has a non-empty body.
+    ///
+    /// Endpoints that are supposed to return data sometimes answer with an
+    /// empty `200`, which [`json()`](Self::json) reports as a confusing parse
+    /// error. Calling this first turns that case into a clear error and hands
+    /// the response back unchanged otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TypeError`] if the body is absent or empty.
+    ///
+    /// [`TypeError`]: crate::TypeError
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Response, ReadableStream};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let response = Response::new(Some(ReadableStream::from_text("{\"id\": 1}")), None).unwrap();
+    /// let value: serde_json::Value = response.require_body().unwrap().json().await.unwrap();
+    /// assert_eq!(value["id"], 1);
+    ///
+    /// let empty = Response::new(None, None).unwrap();
+    /// assert!(empty.require_body().is_err());
+    /// # });
+    /// ```
+    pub fn require_body(self) -> Result<Self> {
+        if self.body.as_ref().is_none_or(|body| body.is_empty()) {
+            return Err(FetchError::Type(TypeError::new(&format!(
+                "Expected a response body, but the {} response from {} was empty",
+                self.status,
+                if self.url.is_empty() {
+                    "(unknown URL)"
+                } else {
+                    &self.url
+                }
+            ))));
+        }
+        Ok(self)
+    }
+
+    /// Consume the response and return the body as a blob (bytes).
     pub async fn blob(self) -> Result<bytes::Bytes> {
         self.array_buffer().await
     }
@@ -634,6 +1432,163 @@ impl Response {
         }
     }
 
+    /// Check whether the response declares a JSON content type.
+    ///
+    /// The `Content-Type` essence is compared case-insensitively, ignoring
+    /// parameters such as `charset`. `application/json`, `text/json` and any
+    /// structured syntax suffix type like `application/vnd.api+json` or
+    /// `application/problem+json` count as JSON.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Headers, Response, ResponseInit};
+    ///
+    /// let mut headers = Headers::new();
+    /// headers.set("Content-Type", "application/json; charset=utf-8").unwrap();
+    /// let mut init = ResponseInit::new();
+    /// init.headers = Some(headers);
+    ///
+    /// let response = Response::new(None, Some(init)).unwrap();
+    /// assert!(response.is_json());
+    /// ```
+    pub fn is_json(&self) -> bool {
+        self.headers
+            .get("content-type")
+            .ok()
+            .flatten()
+            .is_some_and(|value| is_json_content_type(&value))
+    }
+
+    /// Consume the response and parse the body as JSON, requiring a JSON content type.
+    ///
+    /// Like [`json()`], but first checks [`is_json()`] and fails with a
+    /// [`TypeError`] if the response does not declare a JSON content type, so
+    /// an HTML error page is reported as such rather than as a parse error.
+    ///
+    /// [`json()`]: Response::json
+    /// [`is_json()`]: Response::is_json
+    /// [`TypeError`]: crate::TypeError
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Headers, Response, ResponseInit, ReadableStream};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let mut headers = Headers::new();
+    /// headers.set("Content-Type", "application/json").unwrap();
+    /// let mut init = ResponseInit::new();
+    /// init.headers = Some(headers);
+    ///
+    /// let body = ReadableStream::from_json(&serde_json::json!({"ok": true}));
+    /// let response = Response::new(Some(body), Some(init)).unwrap();
+    /// let value: serde_json::Value = response.expect_json().await.unwrap();
+    /// assert_eq!(value["ok"], true);
+    ///
+    /// let response = Response::new(Some(ReadableStream::from_text("<html>")), None).unwrap();
+    /// assert!(response.expect_json::<serde_json::Value>().await.is_err());
+    /// # });
+    /// ```
+    pub async fn expect_json<T: serde::de::DeserializeOwned>(self) -> Result<T> {
+        if !self.is_json() {
+            let content_type = self.headers.get("content-type").ok().flatten();
+            return Err(FetchError::Type(TypeError::new(&format!(
+                "Expected a JSON response, got content type {}",
+                content_type.as_deref().unwrap_or("(none)")
+            ))));
+        }
+        self.json().await
+    }
+
+    /// Consume the response and parse only the first JSON value in the body.
+    ///
+    /// Bytes after the first complete JSON value are ignored, which helps with
+    /// APIs that send a value followed by a newline and trailing data.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Response, ReadableStream};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let response = Response::new(
+    ///     Some(ReadableStream::from_text("{\"ok\": true}\nEOF")),
+    ///     None
+    /// ).unwrap();
+    ///
+    /// let value: serde_json::Value = response.json_first().await.unwrap();
+    /// assert_eq!(value["ok"], true);
+    /// # });
+    /// ```
+    pub async fn json_first<T: serde::de::DeserializeOwned>(self) -> Result<T> {
+        match self.body {
+            Some(body) => body.json_first().await,
+            None => Err(FetchError::Type(TypeError::new(
+                "Unexpected end of JSON input",
+            ))),
+        }
+    }
+
+    /// Consume the response and parse the body as JSON nested at most
+    /// `max_depth` deep.
+    ///
+    /// Use this for bodies from untrusted sources: a payload whose arrays and
+    /// objects nest deeper than `max_depth` fails with a [`TypeError`] before
+    /// it is deserialized, instead of exhausting the stack. See
+    /// [`ReadableStream::json_guarded()`] for how depth is counted.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Response, ReadableStream};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let nested = format!("{}{}", "[".repeat(1000), "]".repeat(1000));
+    /// let response = Response::new(Some(ReadableStream::from_text(&nested)), None).unwrap();
+    ///
+    /// assert!(response.json_guarded::<serde_json::Value>(64).await.is_err());
+    /// # });
+    /// ```
+    pub async fn json_guarded<T: serde::de::DeserializeOwned>(self, max_depth: usize) -> Result<T> {
+        match self.body {
+            Some(body) => body.json_guarded(max_depth).await,
+            None => Err(FetchError::Type(TypeError::new(
+                "Unexpected end of JSON input",
+            ))),
+        }
+    }
+
+    /// Consume the response and decode a top-level JSON array element by element.
+    ///
+    /// See [`ReadableStream::json_array_stream()`] for details. A response
+    /// without a body yields a single error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Response, ReadableStream};
+    /// use futures::StreamExt;
+    ///
+    /// # tokio_test::block_on(async {
+    /// let response = Response::new(
+    ///     Some(ReadableStream::from_text(r#"[{"id": 1}, {"id": 2}]"#)),
+    ///     None
+    /// ).unwrap();
+    ///
+    /// let mut items = Box::pin(response.json_array_stream::<serde_json::Value>());
+    /// while let Some(item) = items.next().await {
+    ///     assert!(item.unwrap()["id"].is_number());
+    /// }
+    /// # });
+    /// ```
+    pub fn json_array_stream<T: serde::de::DeserializeOwned>(
+        self,
+    ) -> impl futures_util::Stream<Item = Result<T>> {
+        let body = self.body.unwrap_or_else(|| ReadableStream::from_text(""));
+        body.json_array_stream()
+    }
+
     /// Consume the response and return the body as text.
     ///
     /// # Examples
@@ -658,6 +1613,121 @@ impl Response {
         }
     }
 
+    /// Consume the response and return the body as text, replacing invalid UTF-8.
+    ///
+    /// Unlike [`text()`], invalid byte sequences do not cause an error; each
+    /// one is replaced with U+FFFD (`�`). This suits logging and diagnostics of
+    /// arbitrary responses. The only error is a [`TypeError`] for a body that
+    /// was already used.
+    ///
+    /// [`text()`]: Response::text
+    /// [`TypeError`]: crate::TypeError
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Bytes, Response, ReadableStream};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let body = ReadableStream::from_bytes(Bytes::from_static(b"ok \xff"));
+    /// let response = Response::new(Some(body), None).unwrap();
+    /// assert_eq!(response.text_lossy().await.unwrap(), "ok \u{FFFD}");
+    /// # });
+    /// ```
+    pub async fn text_lossy(self) -> Result<String> {
+        let bytes = self.array_buffer().await?;
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Consume the response and return the body as text, refusing bodies over `max` bytes.
+    ///
+    /// A guard for endpoints expected to return small text: if the body is
+    /// larger than `max` bytes this fails with a [`NetworkError`] instead of
+    /// decoding it.
+    ///
+    /// [`NetworkError`]: crate::NetworkError
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Response, ReadableStream};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let response = Response::new(Some(ReadableStream::from_text("pong")), None).unwrap();
+    /// assert_eq!(response.text_capped(16).await.unwrap(), "pong");
+    ///
+    /// let response = Response::new(Some(ReadableStream::from_text("too long")), None).unwrap();
+    /// assert!(response.text_capped(4).await.is_err());
+    /// # });
+    /// ```
+    pub async fn text_capped(self, max: usize) -> Result<String> {
+        let bytes = self.array_buffer().await?;
+        if bytes.len() > max {
+            return Err(FetchError::Network(NetworkError::new(&format!(
+                "Response body of {} bytes exceeds the {} byte cap",
+                bytes.len(),
+                max
+            ))));
+        }
+        String::from_utf8(bytes.to_vec())
+            .map_err(|_| FetchError::Type(TypeError::new("Invalid UTF-8")))
+    }
+
+    /// Write the whole body to `writer`, then return the response with its body intact.
+    ///
+    /// The returned response can still be read with [`text()`], [`json()`]
+    /// and friends, so a body can be saved, for example to a file, and then
+    /// parsed. The body is shared rather than copied, so it is not held in
+    /// memory twice.
+    ///
+    /// This is not a streaming tee. Response bodies are fully buffered by the
+    /// time a [`Response`] exists, so the entire body is written and flushed
+    /// before this method returns, not as the returned body is consumed.
+    ///
+    /// [`text()`]: Response::text
+    /// [`json()`]: Response::json
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`TypeError`] if the body was already used and a
+    /// [`NetworkError`] if writing to `writer` fails.
+    ///
+    /// [`TypeError`]: crate::TypeError
+    /// [`NetworkError`]: crate::NetworkError
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::{Response, ReadableStream};
+    ///
+    /// # tokio_test::block_on(async {
+    /// let response = Response::new(Some(ReadableStream::from_text("saved")), None).unwrap();
+    ///
+    /// let mut copy = Vec::new();
+    /// let response = response.write_body_to(&mut copy).await.unwrap();
+    ///
+    /// assert_eq!(copy, b"saved");
+    /// assert_eq!(response.text().await.unwrap(), "saved");
+    /// # });
+    /// ```
+    pub async fn write_body_to<W>(mut self, mut writer: W) -> Result<Self>
+    where
+        W: tokio::io::AsyncWrite + Unpin,
+    {
+        use tokio::io::AsyncWriteExt;
+
+        if let Some(body) = self.body.take() {
+            let bytes = body.array_buffer().await?;
+            let write_error = |e: std::io::Error| {
+                FetchError::Network(NetworkError::new(&format!("Failed to write body: {}", e)))
+            };
+            writer.write_all(&bytes).await.map_err(write_error)?;
+            writer.flush().await.map_err(write_error)?;
+            self.body = Some(ReadableStream::from_bytes(bytes));
+        }
+        Ok(self)
+    }
+
     /// Get the default status text for a status code.
     ///
     /// Returns the standard HTTP reason phrases for common status codes.
@@ -710,6 +1780,24 @@ impl Response {
             status_text,
             headers,
             body: None,
+            body_limit_remaining: None,
+            dns_time: None,
+            version: http::Version::HTTP_11,
+            extensions: http::Extensions::new(),
+        }
+    }
+
+    /// Create an opaque-redirect response (internal use).
+    ///
+    /// Returned for redirects when the request's redirect mode is
+    /// [`RequestRedirect::Manual`](crate::RequestRedirect::Manual). Like the
+    /// spec's opaque-redirect filtered response it has status 0, no headers
+    /// and no body; only the URL of the redirecting response is kept.
+    pub(crate) fn opaque_redirect(url: String) -> Self {
+        Self {
+            response_type: ResponseType::OpaqueRedirect,
+            url,
+            ..Self::error()
         }
     }
 
@@ -720,6 +1808,129 @@ impl Response {
     pub(crate) fn set_body(&mut self, body: ReadableStream) {
         self.body = Some(body);
     }
+
+    /// Record the DNS lookup time of the connection (internal use).
+    pub(crate) fn set_dns_time(&mut self, time: Duration) {
+        self.dns_time = Some(time);
+    }
+
+    /// Record the HTTP version and extensions of the underlying response (internal use).
+    pub(crate) fn set_http_parts(&mut self, version: http::Version, extensions: http::Extensions) {
+        self.version = version;
+        self.extensions = extensions;
+    }
+
+    /// Record the unused part of the body size limit (internal use).
+    pub(crate) fn set_body_limit_remaining(&mut self, remaining: usize) {
+        self.body_limit_remaining = Some(remaining);
+    }
+}
+
+/// Whether a `Content-Type` value has a JSON essence.
+fn is_json_content_type(value: &str) -> bool {
+    let essence = value
+        .split(';')
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    match essence.split_once('/') {
+        Some((_, subtype)) if subtype.ends_with("+json") => true,
+        _ => essence == "application/json" || essence == "text/json",
+    }
+}
+
+/// Split a header value on `;`, ignoring separators inside quoted strings.
+fn split_header_params(value: &str) -> Vec<&str> {
+    let mut params = Vec::new();
+    let mut start = 0;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => {
+                params.push(value[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    params.push(value[start..].trim());
+    params
+}
+
+/// Split a `Link` header value into its comma-separated link values.
+///
+/// Commas inside the `<...>` target or a quoted parameter do not split.
+fn split_link_values(value: &str) -> Vec<&str> {
+    let mut links = Vec::new();
+    let mut start = 0;
+    let mut in_target = false;
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, c) in value.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' if !in_target => in_quotes = !in_quotes,
+            '<' if !in_quotes => in_target = true,
+            '>' if !in_quotes => in_target = false,
+            ',' if !in_quotes && !in_target => {
+                links.push(value[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    links.push(value[start..].trim());
+    links.retain(|link| !link.is_empty());
+    links
+}
+
+/// Strip surrounding quotes from a parameter value and resolve escapes.
+fn unquote(value: &str) -> String {
+    match value
+        .strip_prefix('"')
+        .and_then(|inner| inner.strip_suffix('"'))
+    {
+        Some(inner) => {
+            let mut out = String::with_capacity(inner.len());
+            let mut chars = inner.chars();
+            while let Some(c) = chars.next() {
+                if c == '\\' {
+                    if let Some(next) = chars.next() {
+                        out.push(next);
+                    }
+                } else {
+                    out.push(c);
+                }
+            }
+            out
+        }
+        None => value.to_string(),
+    }
+}
+
+/// Decode an RFC 5987 extended parameter value (`charset'language'value`).
+fn decode_ext_value(value: &str) -> Option<String> {
+    let mut parts = value.splitn(3, '\'');
+    let charset = parts.next()?;
+    let _language = parts.next()?;
+    let encoded = parts.next()?;
+    let bytes: Vec<u8> = percent_encoding::percent_decode_str(encoded).collect();
+
+    if charset.eq_ignore_ascii_case("utf-8") {
+        String::from_utf8(bytes).ok()
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+        Some(bytes.into_iter().map(char::from).collect())
+    } else {
+        None
+    }
 }
 
 impl Clone for Response {
@@ -732,6 +1943,10 @@ impl Clone for Response {
             status_text: self.status_text.clone(),
             headers: self.headers.clone(),
             body: self.body.clone(),
+            body_limit_remaining: self.body_limit_remaining,
+            dns_time: self.dns_time,
+            version: self.version,
+            extensions: self.extensions.clone(),
         }
     }
 }
@@ -776,6 +1991,20 @@ mod tests {
         assert_eq!(response.response_type(), ResponseType::Error);
     }
 
+    #[tokio::test]
+    async fn test_response_error_with() {
+        let body = ReadableStream::from_json(&serde_json::json!({"error": "boom"}));
+        let response = Response::error_with(500, Some(body)).unwrap();
+        assert_eq!(response.status(), 500);
+        assert!(!response.ok());
+        assert_eq!(response.response_type(), ResponseType::Error);
+
+        let parsed: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(parsed["error"], "boom");
+
+        assert!(Response::error_with(0, None).is_err());
+    }
+
     #[test]
     fn test_response_redirect() {
         let response = Response::redirect("https://example.com", Some(301)).unwrap();
@@ -941,6 +2170,42 @@ mod tests {
         assert_eq!(text, "test body");
     }
 
+    #[test]
+    fn test_approx_heap_size() {
+        let empty = Response::new(None, None).unwrap();
+        let small = Response::new(
+            Some(ReadableStream::from_bytes(bytes::Bytes::from(vec![0; 16]))),
+            None,
+        )
+        .unwrap();
+        let large = Response::new(
+            Some(ReadableStream::from_bytes(bytes::Bytes::from(vec![
+                0;
+                1 << 20
+            ]))),
+            None,
+        )
+        .unwrap();
+        assert!(small.approx_heap_size() >= empty.approx_heap_size() + 16);
+        assert!(large.approx_heap_size() >= small.approx_heap_size() + (1 << 20) - 16);
+
+        let mut headers = Headers::new();
+        headers.set("x-padding", &"a".repeat(1000)).unwrap();
+        let mut init = ResponseInit::new();
+        init.headers = Some(headers);
+        let with_headers = Response::new(None, Some(init)).unwrap();
+        assert!(with_headers.approx_heap_size() >= empty.approx_heap_size() + 1000);
+
+        let json = Response::new(
+            Some(ReadableStream::from_json(
+                &serde_json::json!({"items": ["x".repeat(500), "y".repeat(500)]}),
+            )),
+            None,
+        )
+        .unwrap();
+        assert!(json.approx_heap_size() >= empty.approx_heap_size() + 1000);
+    }
+
     #[tokio::test]
     async fn test_response_json_body() {
         let data = serde_json::json!({"key": "value"});
@@ -1018,6 +2283,42 @@ mod tests {
         let _text2 = cloned.text().await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_response_json_first() {
+        let response = Response::new(
+            Some(ReadableStream::from_bytes(bytes::Bytes::from_static(
+                b"[1, 2, 3]\r\n--boundary--",
+            ))),
+            None,
+        )
+        .unwrap();
+        let parsed: Vec<u32> = response.json_first().await.unwrap();
+        assert_eq!(parsed, vec![1, 2, 3]);
+
+        let response = Response::new(None, None).unwrap();
+        let result: Result<serde_json::Value> = response.json_first().await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_response_json_guarded() {
+        // Far deeper than serde_json's own limit, which this must not reach
+        let nested = format!("{}{}", "[".repeat(100_000), "]".repeat(100_000));
+        let response = Response::new(Some(ReadableStream::from_text(&nested)), None).unwrap();
+        let result: Result<serde_json::Value> = response.json_guarded(32).await;
+        assert!(matches!(result, Err(FetchError::Type(_))));
+
+        let shallow = format!("{}{}", "[".repeat(32), "]".repeat(32));
+        let response = Response::new(Some(ReadableStream::from_text(&shallow)), None).unwrap();
+        assert!(response.json_guarded::<serde_json::Value>(32).await.is_ok());
+
+        let response = Response::new(None, None).unwrap();
+        assert!(response
+            .json_guarded::<serde_json::Value>(32)
+            .await
+            .is_err());
+    }
+
     #[tokio::test]
     async fn test_json_empty_body_error() {
         let response = Response::new(None, None).unwrap();
@@ -1046,4 +2347,500 @@ mod tests {
         let unknown_text = Response::default_status_text(999);
         assert_eq!(unknown_text, "");
     }
+
+    fn response_with_disposition(value: &str) -> Response {
+        let mut headers = Headers::new();
+        headers.set("content-disposition", value).unwrap();
+        let mut init = ResponseInit::new();
+        init.headers = Some(headers);
+        Response::new(None, Some(init)).unwrap()
+    }
+
+    #[test]
+    fn test_suggested_filename_plain() {
+        let response = response_with_disposition("attachment; filename=\"x.pdf\"");
+        assert_eq!(response.suggested_filename().unwrap(), "x.pdf");
+
+        let response = response_with_disposition("attachment; filename=data.csv");
+        assert_eq!(response.suggested_filename().unwrap(), "data.csv");
+
+        let response = response_with_disposition("attachment; filename=\"a;b \\\"c\\\".txt\"");
+        assert_eq!(response.suggested_filename().unwrap(), "a;b \"c\".txt");
+    }
+
+    #[test]
+    fn test_suggested_filename_extended() {
+        let response = response_with_disposition(
+            "attachment; filename=\"fallback.txt\"; filename*=UTF-8''na%C3%AFve%20file.txt",
+        );
+        assert_eq!(response.suggested_filename().unwrap(), "naïve file.txt");
+
+        let response = response_with_disposition("attachment; filename*=iso-8859-1'en'%E9t%E9.txt");
+        assert_eq!(response.suggested_filename().unwrap(), "été.txt");
+    }
+
+    #[test]
+    fn test_suggested_filename_missing_or_unsafe() {
+        assert!(Response::new(None, None)
+            .unwrap()
+            .suggested_filename()
+            .is_none());
+        assert!(response_with_disposition("inline")
+            .suggested_filename()
+            .is_none());
+        assert!(response_with_disposition("attachment; filename=\"..\"")
+            .suggested_filename()
+            .is_none());
+
+        let response = response_with_disposition("attachment; filename=\"../../etc/passwd\"");
+        assert_eq!(response.suggested_filename().unwrap(), "passwd");
+    }
+
+    #[tokio::test]
+    async fn test_merge_validated_headers() {
+        let mut cached_headers = Headers::new();
+        cached_headers.set("etag", "\"v1\"").unwrap();
+        cached_headers
+            .set("date", "Mon, 01 Jan 2024 00:00:00 GMT")
+            .unwrap();
+        cached_headers.set("content-type", "text/plain").unwrap();
+        cached_headers.set("content-length", "6").unwrap();
+        let mut init = ResponseInit::new();
+        init.headers = Some(cached_headers);
+        let mut cached =
+            Response::new(Some(ReadableStream::from_text("cached")), Some(init)).unwrap();
+
+        let mut fresh_headers = Headers::new();
+        fresh_headers.set("etag", "\"v2\"").unwrap();
+        fresh_headers
+            .set("date", "Tue, 02 Jan 2024 00:00:00 GMT")
+            .unwrap();
+        fresh_headers.set("content-length", "0").unwrap();
+        fresh_headers
+            .set("connection", "keep-alive, x-hop")
+            .unwrap();
+        fresh_headers.set("keep-alive", "timeout=5").unwrap();
+        fresh_headers.set("transfer-encoding", "chunked").unwrap();
+        fresh_headers.set("x-hop", "1").unwrap();
+        let mut init = ResponseInit::new();
+        init.status = Some(304);
+        init.headers = Some(fresh_headers);
+        let not_modified = Response::new(None, Some(init)).unwrap();
+
+        cached.merge_validated_headers(&not_modified);
+
+        let headers = cached.headers();
+        assert_eq!(headers.get("etag").unwrap().unwrap(), "\"v2\"");
+        assert_eq!(
+            headers.get("date").unwrap().unwrap(),
+            "Tue, 02 Jan 2024 00:00:00 GMT"
+        );
+        assert_eq!(headers.get("content-type").unwrap().unwrap(), "text/plain");
+        assert_eq!(headers.get("content-length").unwrap().unwrap(), "6");
+        assert!(!headers.has("connection").unwrap());
+        assert!(!headers.has("keep-alive").unwrap());
+        assert!(!headers.has("transfer-encoding").unwrap());
+        assert!(!headers.has("x-hop").unwrap());
+
+        assert_eq!(cached.status(), 200);
+        assert_eq!(cached.text().await.unwrap(), "cached");
+    }
+
+    #[tokio::test]
+    async fn test_text_capped() {
+        let response = Response::new(Some(ReadableStream::from_text("12345")), None).unwrap();
+        assert_eq!(response.text_capped(5).await.unwrap(), "12345");
+
+        let response = Response::new(Some(ReadableStream::from_text("123456")), None).unwrap();
+        match response.text_capped(5).await {
+            Err(FetchError::Network(e)) => assert!(e.to_string().contains("5 byte cap")),
+            other => panic!("expected cap error, got {:?}", other),
+        }
+
+        let response = Response::new(None, None).unwrap();
+        assert_eq!(response.text_capped(0).await.unwrap(), "");
+    }
+
+    #[tokio::test]
+    async fn test_write_body_to_keeps_body() {
+        let data = "x".repeat(16 * 1024) + "tail";
+        let response = Response::new(Some(ReadableStream::from_text(&data)), None).unwrap();
+
+        let mut sink = Vec::new();
+        let response = response.write_body_to(&mut sink).await.unwrap();
+        assert!(!response.body_used());
+
+        let text = response.text().await.unwrap();
+        assert_eq!(text, data);
+        assert_eq!(sink, data.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_write_body_to_without_body() {
+        let mut sink = Vec::new();
+        let response = Response::new(None, None)
+            .unwrap()
+            .write_body_to(&mut sink)
+            .await
+            .unwrap();
+        assert!(sink.is_empty());
+        assert_eq!(response.text().await.unwrap(), "");
+    }
+
+    #[tokio::test]
+    async fn test_text_lossy() {
+        let bytes = bytes::Bytes::from_static(b"caf\xc3\xa9 \xc3\x28 end \xff");
+        let response =
+            Response::new(Some(ReadableStream::from_bytes(bytes.clone())), None).unwrap();
+        assert!(response.text().await.is_err());
+
+        let response = Response::new(Some(ReadableStream::from_bytes(bytes)), None).unwrap();
+        let text = response.text_lossy().await.unwrap();
+        assert_eq!(text, "café \u{FFFD}( end \u{FFFD}");
+
+        let response = Response::new(None, None).unwrap();
+        assert_eq!(response.text_lossy().await.unwrap(), "");
+    }
+
+    #[test]
+    fn test_is_json_content_types() {
+        let response_with_type = |value: &str| {
+            let mut headers = Headers::new();
+            headers.set("content-type", value).unwrap();
+            let mut init = ResponseInit::new();
+            init.headers = Some(headers);
+            Response::new(None, Some(init)).unwrap()
+        };
+
+        for value in [
+            "application/json",
+            "application/json; charset=utf-8",
+            "Application/JSON;charset=UTF-8",
+            "application/vnd.api+json",
+            "application/problem+json; charset=utf-8",
+            "text/json",
+        ] {
+            assert!(response_with_type(value).is_json(), "{}", value);
+        }
+
+        for value in [
+            "text/plain",
+            "text/html; charset=utf-8",
+            "application/jsonp",
+            "application/json-seq",
+        ] {
+            assert!(!response_with_type(value).is_json(), "{}", value);
+        }
+        assert!(!Response::new(None, None).unwrap().is_json());
+    }
+
+    #[tokio::test]
+    async fn test_expect_json() {
+        let mut headers = Headers::new();
+        headers
+            .set("content-type", "application/vnd.github+json; charset=utf-8")
+            .unwrap();
+        let mut init = ResponseInit::new();
+        init.headers = Some(headers);
+        let response =
+            Response::new(Some(ReadableStream::from_text("{\"id\": 7}")), Some(init)).unwrap();
+        let value: serde_json::Value = response.expect_json().await.unwrap();
+        assert_eq!(value["id"], 7);
+
+        let response = Response::new(Some(ReadableStream::from_text("{}")), None).unwrap();
+        match response.expect_json::<serde_json::Value>().await {
+            Err(FetchError::Type(e)) => assert!(e.to_string().contains("(none)")),
+            other => panic!("expected content type error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_links() {
+        let mut headers = Headers::new();
+        headers
+            .set(
+                "Link",
+                "</items?page=3>; rel=\"next\", \
+                 <https://api.example.com/items?page=1>; rel=\"prev first\", \
+                 <https://other.example.com/a,b>; rel=NEXT",
+            )
+            .unwrap();
+        let response = Response::from_parts(
+            200,
+            "OK".to_string(),
+            headers,
+            "https://api.example.com/items?page=2".to_string(),
+            false,
+        );
+
+        let links = response.links();
+        assert_eq!(links.len(), 3);
+        assert_eq!(
+            links["next"].as_str(),
+            "https://api.example.com/items?page=3"
+        );
+        assert_eq!(
+            links["prev"].as_str(),
+            "https://api.example.com/items?page=1"
+        );
+        assert_eq!(links["first"], links["prev"]);
+        assert_eq!(
+            response.next_page().unwrap().as_str(),
+            "https://api.example.com/items?page=3"
+        );
+    }
+
+    #[test]
+    fn test_links_missing() {
+        let response = Response::new(None, None).unwrap();
+        assert!(response.links().is_empty());
+        assert!(response.next_page().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_read_into_reused_buffer() {
+        let mut buf = Vec::with_capacity(64);
+        let capacity = buf.capacity();
+
+        let response = Response::new(Some(ReadableStream::from_text("first")), None).unwrap();
+        assert_eq!(response.read_into(&mut buf).await.unwrap(), 5);
+        assert_eq!(buf, b"first");
+
+        buf.clear();
+        let response = Response::new(Some(ReadableStream::from_text("second")), None).unwrap();
+        assert_eq!(response.read_into(&mut buf).await.unwrap(), 6);
+        assert_eq!(buf, b"second");
+        assert_eq!(buf.capacity(), capacity);
+
+        // Appends rather than overwrites, and an empty body writes nothing
+        let response = Response::new(None, None).unwrap();
+        assert_eq!(response.read_into(&mut buf).await.unwrap(), 0);
+        assert_eq!(buf, b"second");
+    }
+
+    #[tokio::test]
+    async fn test_base64_decode() {
+        let body = ReadableStream::from_text("AAEC/96t\r\nvu8=\n");
+        let response = Response::new(Some(body), None).unwrap();
+        let bytes = response.base64_decode().await.unwrap();
+        assert_eq!(bytes, &[0x00, 0x01, 0x02, 0xff, 0xde, 0xad, 0xbe, 0xef][..]);
+
+        let response = Response::new(Some(ReadableStream::from_text("not base64!")), None).unwrap();
+        assert!(matches!(
+            response.base64_decode().await,
+            Err(FetchError::Type(_))
+        ));
+    }
+
+    #[test]
+    fn test_headers_cloned_into_request_init() {
+        let mut headers = Headers::new();
+        headers.set("X-Request-Id", "abc123").unwrap();
+        let mut init = ResponseInit::new();
+        init.headers = Some(headers);
+        let response = Response::new(None, Some(init)).unwrap();
+
+        let mut forwarded = response.headers_cloned();
+        forwarded.set("Accept", "application/json").unwrap();
+        let mut request_init = crate::RequestInit::new();
+        request_init.headers = Some(forwarded);
+        let request = crate::Request::new("https://example.com", Some(request_init)).unwrap();
+
+        assert_eq!(
+            request.headers().get("x-request-id").unwrap().unwrap(),
+            "abc123"
+        );
+        // The clone is independent of the response
+        assert!(!response.headers().has("accept").unwrap());
+    }
+
+    #[test]
+    fn test_require_body() {
+        let response = Response::new(None, None).unwrap();
+        match response.require_body() {
+            Err(FetchError::Type(e)) => assert!(e.message().contains("200"), "{}", e),
+            other => panic!("expected a type error, got {:?}", other.map(|_| ())),
+        }
+
+        let response = Response::new(Some(ReadableStream::from_text("")), None).unwrap();
+        assert!(response.require_body().is_err());
+
+        let response = Response::new(Some(ReadableStream::from_text("data")), None).unwrap();
+        let response = response.require_body().unwrap();
+        assert_eq!(response.status(), 200);
+    }
+
+    #[test]
+    fn test_opaque_redirect() {
+        let response = Response::opaque_redirect("https://example.com/old".to_string());
+        assert_eq!(response.response_type(), ResponseType::OpaqueRedirect);
+        assert_eq!(response.status(), 0);
+        assert!(!response.ok());
+        assert!(!response.redirected());
+        assert_eq!(response.url(), "https://example.com/old");
+        assert!(!response.headers().has("location").unwrap());
+        assert!(response.body().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_json_response() {
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct User {
+            id: u32,
+            name: String,
+        }
+
+        let user = User {
+            id: 7,
+            name: "Ada".to_string(),
+        };
+        let response = Response::json_response(&user, 200).unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(response.ok());
+        assert!(response.is_json());
+        assert_eq!(response.json::<User>().await.unwrap(), user);
+
+        assert!(Response::json_response(&user, 99).is_err());
+
+        let mut bad_key = HashMap::new();
+        bad_key.insert((1, 2), "tuple keys are not valid JSON");
+        assert!(matches!(
+            Response::json_response(&bad_key, 200),
+            Err(FetchError::Type(_))
+        ));
+    }
+
+    #[test]
+    fn test_transfer_framing_from_headers() {
+        let framing = |headers: &[(&str, &str)]| {
+            let mut init = ResponseInit::new();
+            init.headers = Some(Headers::from(headers));
+            Response::new(None, Some(init)).unwrap().transfer_framing()
+        };
+
+        assert_eq!(
+            framing(&[("content-length", "1024")]),
+            TransferFraming::ContentLength(1024)
+        );
+        assert_eq!(
+            framing(&[("transfer-encoding", "chunked")]),
+            TransferFraming::Chunked
+        );
+        assert_eq!(
+            framing(&[
+                ("transfer-encoding", "gzip, chunked"),
+                ("content-length", "10")
+            ]),
+            TransferFraming::Chunked
+        );
+        assert_eq!(
+            framing(&[("transfer-encoding", "gzip")]),
+            TransferFraming::Unknown
+        );
+        assert_eq!(
+            framing(&[("content-length", "nope")]),
+            TransferFraming::Unknown
+        );
+        assert_eq!(framing(&[]), TransferFraming::Unknown);
+    }
+
+    #[test]
+    fn test_hsts() {
+        let hsts = |value: &str| {
+            let mut init = ResponseInit::new();
+            init.headers = Some(Headers::from(&[("strict-transport-security", value)]));
+            Response::new(None, Some(init)).unwrap().hsts()
+        };
+
+        assert_eq!(
+            hsts("max-age=31536000; includeSubDomains; preload"),
+            Some(Hsts {
+                max_age: Duration::from_secs(31536000),
+                include_subdomains: true,
+                preload: true,
+            })
+        );
+        assert_eq!(
+            hsts("MAX-AGE=\"600\";unknown=1"),
+            Some(Hsts {
+                max_age: Duration::from_secs(600),
+                include_subdomains: false,
+                preload: false,
+            })
+        );
+        assert_eq!(hsts("max-age=0").unwrap().max_age, Duration::ZERO);
+        assert!(!hsts("max-age=60, max-age=120; preload").unwrap().preload);
+
+        assert_eq!(hsts("includeSubDomains"), None);
+        assert_eq!(hsts("max-age=-1"), None);
+        assert_eq!(hsts("max-age"), None);
+        assert_eq!(hsts("max-age=1; max-age=2"), None);
+        assert_eq!(Response::new(None, None).unwrap().hsts(), None);
+    }
+
+    #[test]
+    fn test_age_and_date() {
+        let response = |headers: &[(&str, &str)]| {
+            let mut init = ResponseInit::new();
+            init.headers = Some(Headers::from(headers));
+            Response::new(None, Some(init)).unwrap()
+        };
+
+        assert_eq!(
+            response(&[("age", "0")]).age(),
+            Some(Duration::from_secs(0))
+        );
+        assert_eq!(
+            response(&[("age", " 3600 ")]).age(),
+            Some(Duration::from_secs(3600))
+        );
+        assert_eq!(
+            response(&[("age", "99999999999999999999999")]).age(),
+            Some(Duration::from_secs(1 << 31))
+        );
+        for malformed in ["", "-5", "1.5", "ten", "+5", "5, 7"] {
+            assert_eq!(response(&[("age", malformed)]).age(), None, "{}", malformed);
+        }
+        assert_eq!(response(&[]).age(), None);
+
+        let expected = SystemTime::UNIX_EPOCH + Duration::from_secs(784111777);
+        for valid in [
+            "Sun, 06 Nov 1994 08:49:37 GMT",
+            "Sunday, 06-Nov-94 08:49:37 GMT",
+            "Sun Nov  6 08:49:37 1994",
+        ] {
+            assert_eq!(response(&[("date", valid)]).date(), Some(expected));
+        }
+        for malformed in ["yesterday", "Sun, 06 Nov 1994", "1994-11-06T08:49:37Z"] {
+            assert_eq!(
+                response(&[("date", malformed)]).date(),
+                None,
+                "{}",
+                malformed
+            );
+        }
+        assert_eq!(response(&[]).date(), None);
+    }
+
+    #[test]
+    fn test_version_and_extensions() {
+        let response = Response::new(None, None).unwrap();
+        assert_eq!(response.version(), http::Version::HTTP_11);
+        assert!(response.extensions().is_empty());
+
+        let mut response = Response::from_parts(
+            200,
+            "OK".to_string(),
+            Headers::new(),
+            "https://example.com/".to_string(),
+            false,
+        );
+        let mut extensions = http::Extensions::new();
+        extensions.insert(42u32);
+        response.set_http_parts(http::Version::HTTP_2, extensions);
+
+        let cloned = response.clone();
+        assert_eq!(cloned.version(), http::Version::HTTP_2);
+        assert_eq!(cloned.extensions().get::<u32>(), Some(&42));
+    }
 }
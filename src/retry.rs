@@ -0,0 +1,289 @@
+//! Automatic retrying of failed requests.
+//!
+//! A [`RetryPolicy`] attached to a [`FetchClient`](crate::FetchClient) makes
+//! the client re-issue requests that failed in a way that is likely to be
+//! transient. Request bodies are buffered in memory before the first attempt,
+//! so every attempt sends exactly the same bytes, including for `POST`.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use fetchttp::{FetchClient, RetryPolicy};
+//!
+//! let client = FetchClient::builder()
+//!     .retry_policy(Some(RetryPolicy::new(3)))
+//!     .build()
+//!     .unwrap();
+//! ```
+
+use crate::error::{FetchError, Result};
+use std::time::{Duration, SystemTime};
+
+/// Configuration for retrying failed requests.
+///
+/// A request is retried when sending it fails with a
+/// [`NetworkError`](crate::NetworkError) or when the server answers
+/// `429 Too Many Requests` or `503 Service Unavailable`, until `max_attempts`
+/// attempts have been made. Aborted requests are never retried. Which of
+/// these conditions trigger a retry can be changed with
+/// [`retry_network_errors()`](Self::retry_network_errors) and
+/// [`retry_statuses()`](Self::retry_statuses).
+///
+/// Between attempts the client waits with exponential backoff: the first
+/// retry waits [`base_delay()`](Self::base_delay), 100 ms by default, and the
+/// wait doubles with every further retry up to
+/// [`max_delay()`](Self::max_delay), 30 s by default. With
+/// [`jitter()`](Self::jitter), on by default, each wait is randomly shortened
+/// by up to half so that many clients do not retry in lockstep. A retried
+/// response carrying a `Retry-After` header is waited out for exactly the time
+/// it asks for instead; if that is longer than the maximum delay the response
+/// is returned without retrying.
+///
+/// Independently of any policy, an idempotent request whose connection is
+/// reset before a response arrives is sent once more.
+///
+/// # Examples
+///
+/// ```rust
+/// use fetchttp::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new(5)
+///     .base_delay(Duration::from_millis(250))
+///     .max_delay(Duration::from_secs(5))
+///     .retry_statuses([502, 503, 504]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one
+    max_attempts: u32,
+    /// Wait before the first retry
+    base_delay: Duration,
+    /// Upper bound on any wait
+    max_delay: Duration,
+    /// Randomly shorten each backoff wait
+    jitter: bool,
+    /// Retry requests that fail with a network error
+    network_errors: bool,
+    /// Response statuses that are retried
+    statuses: Vec<u16>,
+}
+
+impl RetryPolicy {
+    /// Create a policy allowing up to `max_attempts` attempts in total.
+    ///
+    /// A value of `0` or `1` disables retrying.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use fetchttp::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::new(3);
+    /// assert_eq!(policy.max_attempts(), 3);
+    /// ```
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(30),
+            jitter: true,
+            network_errors: true,
+            statuses: vec![429, 503],
+        }
+    }
+
+    /// Get the total number of attempts allowed.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Set the wait before the first retry, doubled for each later one.
+    ///
+    /// `Duration::ZERO` retries immediately.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set the longest time to wait between two attempts.
+    ///
+    /// Backoff waits are capped at this value, and a `Retry-After` asking
+    /// for longer stops retrying.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Randomly shorten each backoff wait by up to half.
+    pub fn jitter(mut self, enabled: bool) -> Self {
+        self.jitter = enabled;
+        self
+    }
+
+    /// Retry requests that fail with a [`NetworkError`](crate::NetworkError).
+    pub fn retry_network_errors(mut self, enabled: bool) -> Self {
+        self.network_errors = enabled;
+        self
+    }
+
+    /// Retry responses with any of the given statuses.
+    ///
+    /// Replaces the default of `429` and `503`; an empty list retries no
+    /// responses.
+    pub fn retry_statuses(mut self, statuses: impl IntoIterator<Item = u16>) -> Self {
+        self.statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// How long to wait before retrying the outcome of attempt number
+    /// `attempt` (starting at 1), or `None` if it should not be retried.
+    pub(crate) fn retry_delay<T>(
+        &self,
+        attempt: u32,
+        outcome: &Result<(http::response::Parts, T)>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        match outcome {
+            Ok((parts, _)) if self.statuses.contains(&parts.status.as_u16()) => {
+                if let Some(delay) = retry_after(&parts.headers) {
+                    return (delay <= self.max_delay).then_some(delay);
+                }
+            }
+            Err(FetchError::Network(_)) if self.network_errors => {}
+            _ => return None,
+        }
+        Some(self.backoff(attempt))
+    }
+
+    /// Backoff wait after attempt number `attempt`.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt - 1).unwrap_or(u32::MAX);
+        let delay = self
+            .base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay));
+        if self.jitter {
+            delay.mul_f64(1.0 - random_fraction() / 2.0)
+        } else {
+            delay
+        }
+    }
+}
+
+/// Parse a `Retry-After` header, given in seconds or as an HTTP-date.
+fn retry_after(headers: &http::HeaderMap) -> Option<Duration> {
+    let value = headers
+        .get(http::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+/// A random number in `[0, 1)`, good enough for jitter.
+fn random_fraction() -> f64 {
+    use std::hash::{BuildHasher, Hasher};
+
+    let bits = std::collections::hash_map::RandomState::new()
+        .build_hasher()
+        .finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{AbortError, NetworkError};
+
+    fn outcome(status: u16, retry_after: Option<&str>) -> Result<(http::response::Parts, ())> {
+        let mut response = http::Response::builder().status(status);
+        if let Some(value) = retry_after {
+            response = response.header(http::header::RETRY_AFTER, value);
+        }
+        Ok(response.body(()).unwrap().into_parts())
+    }
+
+    #[test]
+    fn test_retry_policy_new() {
+        assert_eq!(RetryPolicy::new(3).max_attempts(), 3);
+        assert_eq!(RetryPolicy::new(0).max_attempts(), 1);
+    }
+
+    #[test]
+    fn test_retry_delay_conditions() {
+        let policy = RetryPolicy::new(3).jitter(false);
+        assert!(policy.retry_delay(1, &outcome(503, None)).is_some());
+        assert!(policy.retry_delay(1, &outcome(429, None)).is_some());
+        assert!(policy.retry_delay(1, &outcome(200, None)).is_none());
+        assert!(policy.retry_delay(1, &outcome(500, None)).is_none());
+        assert!(policy.retry_delay(3, &outcome(503, None)).is_none());
+
+        let network: Result<(http::response::Parts, ())> =
+            Err(FetchError::Network(NetworkError::new("reset")));
+        assert!(policy.retry_delay(2, &network).is_some());
+        let policy = policy.retry_network_errors(false).retry_statuses([500]);
+        assert!(policy.retry_delay(2, &network).is_none());
+        assert!(policy.retry_delay(1, &outcome(500, None)).is_some());
+        assert!(policy.retry_delay(1, &outcome(503, None)).is_none());
+
+        let aborted: Result<(http::response::Parts, ())> =
+            Err(FetchError::Abort(AbortError::new("aborted")));
+        assert!(RetryPolicy::new(3).retry_delay(1, &aborted).is_none());
+    }
+
+    #[test]
+    fn test_exponential_backoff() {
+        let policy = RetryPolicy::new(10)
+            .base_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_millis(500))
+            .jitter(false);
+        let delays: Vec<_> = (1..6)
+            .map(|attempt| policy.retry_delay(attempt, &outcome(503, None)).unwrap())
+            .collect();
+        assert_eq!(delays, [100, 200, 400, 500, 500].map(Duration::from_millis));
+        assert_eq!(policy.backoff(64), Duration::from_millis(500));
+
+        let jittered = policy.jitter(true);
+        for _ in 0..100 {
+            let delay = jittered.backoff(2);
+            assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+        }
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let policy = RetryPolicy::new(3).max_delay(Duration::from_secs(60));
+        assert_eq!(
+            policy.retry_delay(1, &outcome(429, Some("7"))),
+            Some(Duration::from_secs(7))
+        );
+        // Longer than the maximum delay: give up instead of waiting
+        assert_eq!(policy.retry_delay(1, &outcome(503, Some("120"))), None);
+
+        let soon = httpdate::fmt_http_date(SystemTime::now() + Duration::from_secs(10));
+        let delay = policy.retry_delay(1, &outcome(503, Some(&soon))).unwrap();
+        assert!(delay > Duration::from_secs(5) && delay <= Duration::from_secs(10));
+        let past = "Sun, 06 Nov 1994 08:49:37 GMT";
+        assert_eq!(
+            policy.retry_delay(1, &outcome(503, Some(past))),
+            Some(Duration::ZERO)
+        );
+
+        // Unparsable values fall back to the backoff
+        let fallback = policy.jitter(false);
+        assert_eq!(
+            fallback.retry_delay(1, &outcome(503, Some("soon"))),
+            Some(Duration::from_millis(100))
+        );
+    }
+}
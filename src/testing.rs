@@ -0,0 +1,201 @@
+//! Assertion helpers for tests that use this crate.
+//!
+//! Enabled by the `testing` feature, typically as a dev-dependency:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! fetchttp = { version = "*", features = ["testing"] }
+//! ```
+//!
+//! Every helper panics with a message describing the response when the
+//! assertion fails, so test output shows what was actually received.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use fetchttp::testing::{assert_body, assert_header, assert_status};
+//! use fetchttp::{Headers, ReadableStream, Response, ResponseInit};
+//!
+//! # tokio_test::block_on(async {
+//! let mut headers = Headers::new();
+//! headers.set("Content-Type", "application/json").unwrap();
+//! let mut init = ResponseInit::new();
+//! init.headers = Some(headers);
+//! let response = Response::new(Some(ReadableStream::from_text("{}")), Some(init)).unwrap();
+//!
+//! assert_status(&response, 200);
+//! assert_header(&response, "content-type", "application/json");
+//! assert_body(&response, "{}").await;
+//! # });
+//! ```
+
+use crate::Response;
+
+/// Assert that `response` has the given status code.
+///
+/// # Panics
+///
+/// Panics if the status differs, naming the actual status and the URL.
+#[track_caller]
+pub fn assert_status(response: &Response, expected: u16) {
+    if response.status() != expected {
+        panic!(
+            "expected status {}, got {} {} for {}",
+            expected,
+            response.status(),
+            response.status_text(),
+            describe_url(response)
+        );
+    }
+}
+
+/// Assert that header `name` of `response` equals `expected`.
+///
+/// Header names are case-insensitive. Repeated headers are compared in their
+/// combined, comma-separated form.
+///
+/// # Panics
+///
+/// Panics if the header is missing or has a different value.
+#[track_caller]
+pub fn assert_header(response: &Response, name: &str, expected: &str) {
+    match response.headers().get(name) {
+        Ok(Some(value)) if value == expected => {}
+        Ok(Some(value)) => panic!(
+            "expected header {} to be {:?}, got {:?} for {}",
+            name,
+            expected,
+            value,
+            describe_url(response)
+        ),
+        Ok(None) => panic!(
+            "expected header {} to be {:?}, but it is missing for {}",
+            name,
+            expected,
+            describe_url(response)
+        ),
+        Err(e) => panic!("invalid header name {:?}: {}", name, e),
+    }
+}
+
+/// Assert that the body of `response` equals `expected`.
+///
+/// The body is read from a clone, so `response` can still be used afterwards.
+///
+/// # Panics
+///
+/// Panics if the body cannot be read or differs from `expected`. Bodies that
+/// are valid UTF-8 are shown as text in the message, others as bytes.
+pub async fn assert_body(response: &Response, expected: impl AsRef<[u8]>) {
+    let expected = expected.as_ref();
+    let body = match response.clone().array_buffer().await {
+        Ok(body) => body,
+        Err(e) => panic!("failed to read body of {}: {}", describe_url(response), e),
+    };
+    if body != expected {
+        panic!(
+            "expected body {}, got {} for {}",
+            describe_bytes(expected),
+            describe_bytes(&body),
+            describe_url(response)
+        );
+    }
+}
+
+/// Describe the URL of `response` for assertion messages.
+fn describe_url(response: &Response) -> String {
+    if response.url().is_empty() {
+        "response without URL".to_string()
+    } else {
+        response.url().to_string()
+    }
+}
+
+/// Render bytes as quoted text when they are UTF-8, or as a byte list otherwise.
+fn describe_bytes(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => format!("{:?}", text),
+        Err(_) => format!("{:?}", bytes),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Headers, ReadableStream, ResponseInit};
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    fn response(status: u16, body: &str) -> Response {
+        let mut headers = Headers::new();
+        headers.set("Content-Type", "application/json").unwrap();
+        let mut init = ResponseInit::new();
+        init.status = Some(status);
+        init.headers = Some(headers);
+        Response::new(Some(ReadableStream::from_text(body)), Some(init)).unwrap()
+    }
+
+    /// Run `f` and return its panic message, if it panicked.
+    fn panic_message(f: impl FnOnce()) -> Option<String> {
+        let payload = catch_unwind(AssertUnwindSafe(f)).err()?;
+        Some(
+            payload
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_default(),
+        )
+    }
+
+    fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    #[test]
+    fn test_assert_status() {
+        let response = response(404, "");
+        assert!(panic_message(|| assert_status(&response, 404)).is_none());
+
+        let message = panic_message(|| assert_status(&response, 200)).unwrap();
+        assert_eq!(
+            message,
+            "expected status 200, got 404 Not Found for response without URL"
+        );
+    }
+
+    #[test]
+    fn test_assert_header() {
+        let response = response(200, "");
+        assert!(
+            panic_message(|| assert_header(&response, "content-type", "application/json"))
+                .is_none()
+        );
+
+        let message =
+            panic_message(|| assert_header(&response, "Content-Type", "text/plain")).unwrap();
+        assert!(message.contains("\"text/plain\""), "{}", message);
+        assert!(message.contains("\"application/json\""), "{}", message);
+
+        let message = panic_message(|| assert_header(&response, "etag", "\"v1\"")).unwrap();
+        assert!(message.contains("missing"), "{}", message);
+    }
+
+    #[test]
+    fn test_assert_body() {
+        let response = response(200, "{\"ok\":true}");
+        assert!(panic_message(|| block_on(assert_body(&response, "{\"ok\":true}"))).is_none());
+        // The response is still usable after a passing assertion
+        assert!(panic_message(|| block_on(assert_body(&response, b"{\"ok\":true}"))).is_none());
+
+        let message = panic_message(|| block_on(assert_body(&response, "{}"))).unwrap();
+        assert_eq!(
+            message,
+            "expected body \"{}\", got \"{\\\"ok\\\":true}\" for response without URL"
+        );
+
+        let message = panic_message(|| block_on(assert_body(&response, [0xffu8]))).unwrap();
+        assert!(message.starts_with("expected body [255]"), "{}", message);
+    }
+}
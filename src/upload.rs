@@ -0,0 +1,150 @@
+//! Request body plumbing for uploads.
+//!
+//! Request bodies are buffered before they are sent. This module hands them to
+//! hyper either as a single frame or as a series of fixed-size chunks, and can
+//! report when hyper pulls each chunk so that stalled uploads can be detected.
+
+use bytes::Bytes;
+use hyper::body::{Body, Frame, SizeHint};
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// Chunk size used when a request body is streamed.
+pub(crate) const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A buffered request body, sent whole or in chunks.
+#[derive(Debug)]
+pub(crate) struct UploadBody {
+    /// Bytes not yet handed to hyper
+    data: Bytes,
+    /// Maximum frame size, or `None` to send the body as one frame
+    chunk_size: Option<usize>,
+    /// Time at which hyper last pulled a chunk, `None` before the first one
+    progress: Option<watch::Sender<Option<Instant>>>,
+}
+
+impl UploadBody {
+    /// Create a body sending `data` as a single frame.
+    pub(crate) fn new(data: Bytes) -> Self {
+        Self {
+            data,
+            chunk_size: None,
+            progress: None,
+        }
+    }
+
+    /// Send the body in frames of at most `chunk_size` bytes.
+    pub(crate) fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = Some(chunk_size.max(1));
+        self
+    }
+
+    /// Start reporting when hyper pulls chunks of the body.
+    ///
+    /// The returned receiver is for [`stalled()`].
+    pub(crate) fn track_progress(&mut self) -> watch::Receiver<Option<Instant>> {
+        let (sender, receiver) = watch::channel(None);
+        self.progress = Some(sender);
+        receiver
+    }
+
+    /// Take the remaining bytes of the body.
+    pub(crate) fn into_bytes(self) -> Bytes {
+        self.data
+    }
+}
+
+impl Body for UploadBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        if self.data.is_empty() {
+            return Poll::Ready(None);
+        }
+
+        let len = self
+            .chunk_size
+            .map_or(self.data.len(), |size| size.min(self.data.len()));
+        let chunk = self.data.split_to(len);
+        if let Some(progress) = &self.progress {
+            progress.send_replace(Some(Instant::now()));
+        }
+        Poll::Ready(Some(Ok(Frame::data(chunk))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.data.len() as u64)
+    }
+}
+
+/// Resolve once the tracked upload has made no progress for `timeout`.
+///
+/// The clock starts when hyper pulls the first chunk, so time spent
+/// connecting is not counted. Never resolves once the body has been dropped,
+/// which hyper does after sending its last chunk.
+pub(crate) async fn stalled(mut progress: watch::Receiver<Option<Instant>>, timeout: Duration) {
+    loop {
+        let last = *progress.borrow_and_update();
+        let changed = match last {
+            Some(last) => tokio::select! {
+                changed = progress.changed() => changed,
+                _ = tokio::time::sleep_until((last + timeout).into()) => return,
+            },
+            None => progress.changed().await,
+        };
+        if changed.is_err() {
+            return std::future::pending().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http_body_util::BodyExt;
+
+    #[tokio::test]
+    async fn test_upload_body_chunks() {
+        let mut body = UploadBody::new(Bytes::from_static(b"abcdefgh")).with_chunk_size(3);
+        assert_eq!(body.size_hint().exact(), Some(8));
+
+        let mut chunks = Vec::new();
+        while let Some(frame) = body.frame().await {
+            chunks.push(frame.unwrap().into_data().unwrap());
+        }
+        assert_eq!(chunks, vec!["abc", "def", "gh"]);
+        assert!(body.is_end_stream());
+    }
+
+    #[tokio::test]
+    async fn test_stalled_after_progress_stops() {
+        let mut body = UploadBody::new(Bytes::from_static(b"abcd")).with_chunk_size(2);
+        let progress = body.track_progress();
+        body.frame().await.unwrap().unwrap();
+
+        let timeout = Duration::from_millis(20);
+        tokio::time::timeout(Duration::from_secs(5), stalled(progress, timeout))
+            .await
+            .expect("stalled upload not detected");
+
+        // Dropping the body means it was fully handed off
+        let progress = body.track_progress();
+        drop(body);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), stalled(progress, timeout))
+                .await
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,53 @@
+//! Tests for the process-wide default timeout.
+//!
+//! These live in their own test binary because the default timeout is global
+//! state that would affect every other test running in the same process.
+
+use fetchttp::*;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test]
+async fn test_set_default_timeout() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/slow"))
+        .respond_with(ResponseTemplate::new(200).set_delay(Duration::from_secs(2)))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/fast"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    // Initialize the shared client before the timeout is configured
+    let response = fetch(&format!("{}/fast", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    set_default_timeout(Duration::from_millis(200));
+
+    let result = fetch(&format!("{}/slow", mock_server.uri()), None).await;
+    match result {
+        Err(FetchError::Network(e)) => assert!(e.to_string().contains("timed out")),
+        other => panic!("expected timeout, got ok={}", other.is_ok()),
+    }
+
+    let response = fetch(&format!("{}/fast", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Clients with their own configuration are unaffected
+    let client = FetchClient::new();
+    let response = client
+        .fetch(&format!("{}/slow", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+}
@@ -1,9 +1,94 @@
 //! Integration tests with real HTTP server
 
 use fetchttp::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
 use wiremock::matchers::{body_string, header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
+/// Start a raw HTTP/1.1 server for responses wiremock cannot produce.
+///
+/// `respond` receives the connection index and the raw request head and
+/// returns the exact bytes to write back. A response containing
+/// `connection: close` closes the connection after it is written. Returns the
+/// server base URL and a counter of accepted connections.
+async fn raw_server<F>(respond: F) -> (String, Arc<AtomicUsize>)
+where
+    F: Fn(usize, &str) -> Vec<u8> + Send + Sync + 'static,
+{
+    raw_server_on("127.0.0.1:0", respond).await
+}
+
+/// Like [`raw_server`], but listening on the given address.
+async fn raw_server_on<F>(bind: &str, respond: F) -> (String, Arc<AtomicUsize>)
+where
+    F: Fn(usize, &str) -> Vec<u8> + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind(bind).await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let connections = Arc::new(AtomicUsize::new(0));
+    let respond = Arc::new(respond);
+
+    let counter = connections.clone();
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(_) => return,
+            };
+            let index = counter.fetch_add(1, Ordering::SeqCst);
+            let respond = respond.clone();
+            tokio::spawn(async move {
+                let mut buf = Vec::new();
+                loop {
+                    // Read one request head and its Content-Length body
+                    let head_end = loop {
+                        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                            break pos + 4;
+                        }
+                        let mut chunk = [0u8; 4096];
+                        match socket.read(&mut chunk).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                        }
+                    };
+                    let head = String::from_utf8_lossy(&buf[..head_end]).to_string();
+                    let body_len = head
+                        .lines()
+                        .find_map(|line| {
+                            let (name, value) = line.split_once(':')?;
+                            name.eq_ignore_ascii_case("content-length")
+                                .then(|| value.trim().parse::<usize>().ok())?
+                        })
+                        .unwrap_or(0);
+                    while buf.len() < head_end + body_len {
+                        let mut chunk = [0u8; 4096];
+                        match socket.read(&mut chunk).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                        }
+                    }
+                    buf.drain(..head_end + body_len);
+
+                    let response = respond(index, &head);
+                    if socket.write_all(&response).await.is_err() {
+                        return;
+                    }
+                    let text = String::from_utf8_lossy(&response).to_ascii_lowercase();
+                    if text.contains("connection: close") {
+                        let _ = socket.shutdown().await;
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    (format!("http://{}", addr), connections)
+}
+
 #[tokio::test]
 async fn test_fetch_get_request() {
     let mock_server = MockServer::start().await;
@@ -151,9 +236,146 @@ async fn test_fetch_redirect() {
         .await
         .unwrap();
 
-    // Note: This behavior depends on the underlying HTTP client's redirect handling
-    // In a real implementation, we might need to handle redirects manually
-    assert!(response.status() == 302 || response.status() == 200);
+    assert_eq!(response.status(), 200);
+    assert!(response.redirected());
+    assert_eq!(response.url(), format!("{}/target", mock_server.uri()));
+    assert_eq!(response.text().await.unwrap(), "Redirected");
+
+    // Manual mode hands back an opaque-redirect response instead
+    let mut init = RequestInit::new();
+    init.redirect = Some(RequestRedirect::Manual);
+    let response = fetch(&format!("{}/redirect", mock_server.uri()), Some(init))
+        .await
+        .unwrap();
+    assert_eq!(response.response_type(), ResponseType::OpaqueRedirect);
+    assert_eq!(response.status(), 0);
+    assert!(!response.redirected());
+    assert_eq!(response.url(), format!("{}/redirect", mock_server.uri()));
+    assert!(!response.headers().has("location").unwrap());
+    assert!(response.array_buffer().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_fetch_redirect_method_rewriting() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/see-other"))
+        .respond_with(ResponseTemplate::new(303).insert_header("location", "/result"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/result"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("got"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/temporary"))
+        .respond_with(ResponseTemplate::new(307).insert_header("location", "/moved"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("POST"))
+        .and(path("/moved"))
+        .and(body_string("payload"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("posted"))
+        .mount(&mock_server)
+        .await;
+
+    let post = || {
+        let mut init = RequestInit::new();
+        init.method = Some("POST".to_string());
+        init.body = Some(ReadableStream::from_text("payload"));
+        Some(init)
+    };
+
+    // 303 switches to a bodiless GET
+    let response = fetch(&format!("{}/see-other", mock_server.uri()), post())
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "got");
+
+    // 307 preserves the method and body
+    let response = fetch(&format!("{}/temporary", mock_server.uri()), post())
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "posted");
+}
+
+#[tokio::test]
+async fn test_fetch_redirect_to_get_drops_digest_header() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/upload"))
+        .and(header("x-amz-content-sha256", SHA256_PAYLOAD))
+        .respond_with(ResponseTemplate::new(303).insert_header("location", "/done"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/done"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("done"))
+        .mount(&mock_server)
+        .await;
+
+    let mut init = RequestInit::new();
+    init.method = Some("POST".to_string());
+    init.body = Some(ReadableStream::from_text("payload"));
+    init.content_sha256_header = Some("x-amz-content-sha256".to_string());
+    let response = fetch(&format!("{}/upload", mock_server.uri()), Some(init))
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "done");
+
+    let requests = mock_server.received_requests().await.unwrap();
+    let get = requests
+        .iter()
+        .find(|request| request.url.path() == "/done")
+        .unwrap();
+    assert!(!get.headers.contains_key("x-amz-content-sha256"));
+}
+
+/// SHA-256 of `payload`, hex-encoded.
+const SHA256_PAYLOAD: &str = "239f59ed55e737c77147cf55ad0c1b030b6d7ee748a7426952f9b852d5a935e5";
+
+#[tokio::test]
+async fn test_fetch_traced_redirect_chain() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/first"))
+        .respond_with(ResponseTemplate::new(301).insert_header("location", "/second"))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/second"))
+        .respond_with(ResponseTemplate::new(302).insert_header("location", "/final"))
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/final"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("Arrived"))
+        .mount(&mock_server)
+        .await;
+
+    let base = mock_server.uri();
+    let (response, chain) = fetch_traced(&format!("{}/first", base), None)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        chain,
+        vec![
+            (301, format!("{}/first", base)),
+            (302, format!("{}/second", base)),
+            (200, format!("{}/final", base)),
+        ]
+    );
+    assert_eq!(response.status(), 200);
+    assert!(response.redirected());
+    assert_eq!(response.url(), format!("{}/final", base));
+    assert_eq!(response.text().await.unwrap(), "Arrived");
 }
 
 #[tokio::test]
@@ -207,6 +429,402 @@ async fn test_fetch_response_headers() {
     assert!(headers.has("content-type").unwrap());
 }
 
+#[tokio::test]
+async fn test_fetch_client_socket_options() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/client"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("From client"))
+        .mount(&mock_server)
+        .await;
+
+    let client = FetchClient::builder()
+        .tcp_nodelay(true)
+        .tcp_keepalive(Some(std::time::Duration::from_secs(30)))
+        .build()
+        .unwrap();
+
+    let response = client
+        .fetch(&format!("{}/client", mock_server.uri()), None)
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "From client");
+}
+
+#[tokio::test]
+async fn test_fetch_with_external_hyper_client() {
+    use hyper_util::client::legacy::connect::HttpConnector;
+    use hyper_util::client::legacy::Client;
+    use hyper_util::rt::TokioExecutor;
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/external"))
+        .and(body_string("payload"))
+        .respond_with(ResponseTemplate::new(201).set_body_string("created"))
+        .mount(&mock_server)
+        .await;
+
+    let mut connector = HttpConnector::new();
+    connector.set_nodelay(true);
+    let client: Client<_, http_body_util::Full<Bytes>> =
+        Client::builder(TokioExecutor::new()).build(connector);
+
+    let mut init = RequestInit::new();
+    init.method = Some("POST".to_string());
+    init.body = Some(ReadableStream::from_text("payload"));
+    let response = fetch_with_client(
+        &client,
+        &format!("{}/external", mock_server.uri()),
+        Some(init),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(response.status(), 201);
+    assert_eq!(response.text().await.unwrap(), "created");
+}
+
+#[tokio::test]
+async fn test_fetch_client_user_agent() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/default"))
+        .and(header("user-agent", "my-service/1.0"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/override"))
+        .and(header("user-agent", "custom/2.0"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let client = FetchClient::builder()
+        .user_agent("my-service/1.0")
+        .build()
+        .unwrap();
+
+    let response = client
+        .fetch(&format!("{}/default", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let mut init = RequestInit::new();
+    init.headers = Some(Headers::from(&[("User-Agent", "custom/2.0")]));
+    let response = client
+        .fetch(&format!("{}/override", mock_server.uri()), Some(init))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_fetch_client_default_headers() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/default"))
+        .and(header("user-agent", "MyApp/1.0"))
+        .and(header("x-api-version", "2"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/override"))
+        .and(header("user-agent", "MyApp/1.0"))
+        .and(header("x-api-version", "3"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let client = FetchClient::builder()
+        .default_headers(Headers::from(&[
+            ("User-Agent", "MyApp/1.0"),
+            ("X-Api-Version", "2"),
+        ]))
+        .build()
+        .unwrap();
+
+    let response = client
+        .fetch(&format!("{}/default", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // Per-request headers win over the defaults
+    let mut init = RequestInit::new();
+    init.headers = Some(Headers::from(&[("x-API-version", "3")]));
+    let response = client
+        .fetch(&format!("{}/override", mock_server.uri()), Some(init))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+/// Start an HTTPS server with a freshly generated self-signed certificate.
+///
+/// Every request is answered with `200 OK` and the body `secure`. Returns the
+/// server base URL.
+async fn self_signed_tls_server() -> String {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+    tls_server(cert.cert.der().clone(), cert.signing_key.serialize_der()).await
+}
+
+/// Start an HTTPS server for `localhost` whose certificate is signed by a
+/// freshly generated CA. Returns the server URL and the CA certificate PEM.
+async fn ca_signed_tls_server() -> (String, String) {
+    let ca_key = rcgen::KeyPair::generate().unwrap();
+    let mut ca_params = rcgen::CertificateParams::new(Vec::<String>::new()).unwrap();
+    ca_params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+    ca_params.key_usages = vec![
+        rcgen::KeyUsagePurpose::KeyCertSign,
+        rcgen::KeyUsagePurpose::CrlSign,
+    ];
+    ca_params
+        .distinguished_name
+        .push(rcgen::DnType::CommonName, "fetchttp test CA");
+    let ca_cert = ca_params.self_signed(&ca_key).unwrap();
+    let issuer = rcgen::Issuer::new(ca_params, ca_key);
+
+    let key = rcgen::KeyPair::generate().unwrap();
+    let cert = rcgen::CertificateParams::new(vec!["localhost".to_string()])
+        .unwrap()
+        .signed_by(&key, &issuer)
+        .unwrap();
+    let url = tls_server(cert.der().clone(), key.serialize_der()).await;
+    (url, ca_cert.pem())
+}
+
+/// Start an HTTPS server presenting `cert` that answers every request with
+/// `secure`, returning its `localhost` URL.
+async fn tls_server(
+    cert: tokio_rustls::rustls::pki_types::CertificateDer<'static>,
+    key: Vec<u8>,
+) -> String {
+    let key = tokio_rustls::rustls::pki_types::PrivateKeyDer::try_from(key).unwrap();
+    let config = tokio_rustls::rustls::ServerConfig::builder_with_provider(Arc::new(
+        tokio_rustls::rustls::crypto::ring::default_provider(),
+    ))
+    .with_safe_default_protocol_versions()
+    .unwrap()
+    .with_no_client_auth()
+    .with_single_cert(vec![cert], key)
+    .unwrap();
+    let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            let acceptor = acceptor.clone();
+            tokio::spawn(async move {
+                let Ok(mut stream) = acceptor.accept(socket).await else {
+                    return;
+                };
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).await;
+                let _ = stream
+                    .write_all(
+                        b"HTTP/1.1 200 OK\r\ncontent-length: 6\r\nconnection: close\r\n\r\nsecure",
+                    )
+                    .await;
+                let _ = stream.shutdown().await;
+            });
+        }
+    });
+
+    format!("https://localhost:{}", port)
+}
+
+#[tokio::test]
+async fn test_danger_accept_invalid_certs() {
+    let base = self_signed_tls_server().await;
+
+    let strict = FetchClient::new();
+    let result = strict.fetch(&base, None).await;
+    assert!(matches!(result, Err(FetchError::Network(_))));
+
+    let insecure = FetchClient::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap();
+    let response = insecure.fetch(&base, None).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "secure");
+}
+
+/// Start an HTTP proxy that only handles `CONNECT`.
+///
+/// Each tunnel is opened to the requested host and port, then bytes are
+/// copied both ways. Returns the proxy URL and the received request heads.
+async fn connect_proxy() -> (String, Arc<std::sync::Mutex<Vec<String>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let heads = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    let seen = heads.clone();
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let seen = seen.clone();
+            tokio::spawn(async move {
+                let mut buf = Vec::new();
+                while !buf.windows(4).any(|w| w == b"\r\n\r\n") {
+                    let mut chunk = [0u8; 1024];
+                    match socket.read(&mut chunk).await {
+                        Ok(0) | Err(_) => return,
+                        Ok(n) => buf.extend_from_slice(&chunk[..n]),
+                    }
+                }
+                let head = String::from_utf8_lossy(&buf).to_string();
+                seen.lock().unwrap().push(head.clone());
+                let target = head.split_whitespace().nth(1).unwrap().to_string();
+                let Ok(mut upstream) = tokio::net::TcpStream::connect(target).await else {
+                    return;
+                };
+                let _ = socket
+                    .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+                    .await;
+                let _ = tokio::io::copy_bidirectional(&mut socket, &mut upstream).await;
+            });
+        }
+    });
+
+    (format!("http://user:pass@{}", addr), heads)
+}
+
+#[tokio::test]
+async fn test_proxy_forwards_http_requests() {
+    let (proxy, connections) = raw_server(|_, head| {
+        let body = head.to_string();
+        format!(
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .into_bytes()
+    })
+    .await;
+    let proxy = proxy.replace("http://", "http://user:pass@");
+
+    let client = FetchClient::builder().proxy(proxy).build().unwrap();
+    let response = client
+        .fetch("http://example.invalid/path?q=1", None)
+        .await
+        .unwrap();
+    let head = response.text().await.unwrap();
+
+    // The proxy receives the absolute URL and the credentials
+    assert!(head.starts_with("GET http://example.invalid/path?q=1 HTTP/1.1\r\n"));
+    assert!(head
+        .to_lowercase()
+        .contains("proxy-authorization: basic dxnlcjpwyxnz\r\n"));
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_proxy_tunnels_https_requests() {
+    let base = self_signed_tls_server().await;
+    let (proxy, heads) = connect_proxy().await;
+
+    let client = FetchClient::builder()
+        .proxy(proxy)
+        .danger_accept_invalid_certs(true)
+        .build()
+        .unwrap();
+    let response = client.fetch(&base, None).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "secure");
+
+    let heads = heads.lock().unwrap();
+    assert_eq!(heads.len(), 1);
+    let port = base.rsplit(':').next().unwrap();
+    assert!(heads[0].starts_with(&format!("CONNECT localhost:{} HTTP/1.1\r\n", port)));
+    assert!(heads[0]
+        .to_lowercase()
+        .contains("proxy-authorization: basic dxnlcjpwyxnz\r\n"));
+}
+
+#[tokio::test]
+async fn test_add_root_certificate() {
+    let (base, ca_pem) = ca_signed_tls_server().await;
+
+    let strict = FetchClient::new();
+    let result = strict.fetch(&base, None).await;
+    assert!(matches!(result, Err(FetchError::Network(_))));
+
+    let trusting = FetchClient::builder()
+        .add_root_certificate(ca_pem.as_bytes())
+        .build()
+        .unwrap();
+    let response = trusting.fetch(&base, None).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "secure");
+
+    // Every certificate of a PEM bundle is trusted, not just the first
+    let other = rcgen::generate_simple_self_signed(vec!["other.test".to_string()]).unwrap();
+    let bundle = format!("{}{}", other.cert.pem(), ca_pem);
+    let trusting = FetchClient::builder()
+        .add_root_certificate(bundle.as_bytes())
+        .build()
+        .unwrap();
+    let response = trusting.fetch(&base, None).await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "secure");
+
+    let result = FetchClient::builder()
+        .add_root_certificate(b"not a certificate")
+        .build();
+    assert!(matches!(result, Err(FetchError::Type(_))));
+}
+
+#[tokio::test]
+async fn test_use_env_proxy() {
+    let (proxy, proxied) = raw_server(|_, head| {
+        let target = head.split_whitespace().nth(1).unwrap().to_string();
+        format!(
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+            target.len(),
+            target
+        )
+        .into_bytes()
+    })
+    .await;
+    let (direct, _) =
+        raw_server(|_, _| b"HTTP/1.1 200 OK\r\ncontent-length: 6\r\n\r\ndirect".to_vec()).await;
+    let direct = direct.replace("127.0.0.1", "localhost");
+
+    // Only clients opting in read the variables, so other tests are unaffected
+    std::env::set_var("http_proxy", proxy.trim_start_matches("http://"));
+    std::env::set_var("no_proxy", ".internal, localhost");
+    let client = FetchClient::builder().use_env_proxy(true).build().unwrap();
+    std::env::remove_var("http_proxy");
+    std::env::remove_var("no_proxy");
+
+    let response = client
+        .fetch("http://service.example.invalid/status", None)
+        .await
+        .unwrap();
+    assert_eq!(
+        response.text().await.unwrap(),
+        "http://service.example.invalid/status"
+    );
+    assert_eq!(proxied.load(Ordering::SeqCst), 1);
+
+    // Exact hosts and domain suffixes in NO_PROXY are connected to directly
+    let response = client.fetch(&direct, None).await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "direct");
+    let result = client.fetch("http://db.internal/", None).await;
+    assert!(matches!(result, Err(FetchError::Network(_))));
+    assert_eq!(proxied.load(Ordering::SeqCst), 1);
+}
+
 #[tokio::test]
 async fn test_request_clone() {
     let request = Request::new("https://example.com", None).unwrap();
@@ -236,3 +854,1628 @@ async fn test_body_consumption() {
 
     // Note: After consumption, the response is moved and can't be accessed again
 }
+
+#[tokio::test]
+async fn test_failed_body_reads_do_not_poison_pool() {
+    let (base, connections) = raw_server(|_, head| {
+        if head.starts_with("GET /truncated") {
+            b"HTTP/1.1 200 OK\r\ncontent-length: 100\r\nconnection: close\r\n\r\npartial".to_vec()
+        } else if head.starts_with("GET /stall") {
+            // Headers and part of the body, then the connection goes quiet
+            b"HTTP/1.1 200 OK\r\ncontent-length: 100\r\n\r\npartial".to_vec()
+        } else {
+            b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok".to_vec()
+        }
+    })
+    .await;
+
+    let client = FetchClient::new();
+    for _ in 0..25 {
+        let result = client.fetch(&format!("{}/truncated", base), None).await;
+        assert!(matches!(result, Err(FetchError::Network(_))));
+    }
+    for _ in 0..25 {
+        let url = format!("{}/stall", base);
+        let result = tokio::time::timeout(
+            std::time::Duration::from_millis(20),
+            client.fetch(&url, None),
+        )
+        .await;
+        assert!(result.is_err(), "stalled body should time out");
+    }
+
+    // Every failed exchange used its own connection and none was pooled
+    assert_eq!(connections.load(Ordering::SeqCst), 50);
+
+    for _ in 0..5 {
+        let response = client.fetch(&format!("{}/ok", base), None).await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "ok");
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 51);
+}
+
+#[tokio::test]
+async fn test_max_response_bytes_without_content_length() {
+    let (base, _) = raw_server(|_, head| {
+        let chunk = "x".repeat(64);
+        let body = if head.starts_with("GET /small") {
+            format!("40\r\n{}\r\n0\r\n\r\n", chunk)
+        } else {
+            format!("40\r\n{c}\r\n40\r\n{c}\r\n0\r\n\r\n", c = chunk)
+        };
+        format!(
+            "HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\n\r\n{}",
+            body
+        )
+        .into_bytes()
+    })
+    .await;
+
+    let client = FetchClient::builder()
+        .max_response_bytes(Some(100))
+        .build()
+        .unwrap();
+
+    let response = client
+        .fetch(&format!("{}/small", base), None)
+        .await
+        .unwrap();
+    assert_eq!(response.body_limit_remaining(), Some(36));
+    assert_eq!(response.text().await.unwrap().len(), 64);
+
+    let result = client.fetch(&format!("{}/large", base), None).await;
+    match result {
+        Err(FetchError::Network(e)) => assert!(e.to_string().contains("max_response_bytes")),
+        other => panic!(
+            "expected body limit error, got {:?}",
+            other.map(|r| r.status())
+        ),
+    }
+}
+
+#[tokio::test]
+async fn test_max_response_bytes_content_length() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/big"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("y".repeat(500)))
+        .mount(&mock_server)
+        .await;
+
+    let client = FetchClient::builder()
+        .max_response_bytes(Some(100))
+        .build()
+        .unwrap();
+    let result = client
+        .fetch(&format!("{}/big", mock_server.uri()), None)
+        .await;
+    assert!(matches!(result, Err(FetchError::Network(_))));
+}
+
+#[cfg(feature = "compression")]
+#[tokio::test]
+async fn test_max_response_bytes_decompression_bomb() {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+    encoder.write_all(&vec![0u8; 1 << 20]).unwrap();
+    let bomb = encoder.finish().unwrap();
+    assert!(bomb.len() < 4096);
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/bomb"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-encoding", "gzip")
+                .set_body_bytes(bomb),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = FetchClient::builder()
+        .max_response_bytes(Some(4096))
+        .build()
+        .unwrap();
+    let result = client
+        .fetch(&format!("{}/bomb", mock_server.uri()), None)
+        .await;
+    match result {
+        Err(FetchError::Network(e)) => assert!(e.to_string().contains("max_response_bytes")),
+        other => panic!(
+            "expected body limit error, got {:?}",
+            other.map(|r| r.status())
+        ),
+    }
+}
+
+#[tokio::test]
+async fn test_dns_time_for_hostname() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/dns"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let port = mock_server.address().port();
+    let client = FetchClient::new();
+
+    let response = client
+        .fetch(&format!("http://localhost:{}/dns", port), None)
+        .await
+        .unwrap();
+    assert!(response.dns_time().is_some());
+
+    // IP literals skip resolution entirely
+    let fresh = FetchClient::new();
+    let response = fresh
+        .fetch(&format!("http://127.0.0.1:{}/dns", port), None)
+        .await
+        .unwrap();
+    assert!(response.dns_time().is_none());
+}
+
+#[tokio::test]
+async fn test_fetch_client_local_address() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/bound"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("bound"))
+        .mount(&mock_server)
+        .await;
+
+    let client = FetchClient::builder()
+        .local_address(Some(std::net::Ipv4Addr::LOCALHOST.into()))
+        .build()
+        .unwrap();
+
+    let response = client
+        .fetch(&format!("{}/bound", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "bound");
+}
+
+#[tokio::test]
+async fn test_json_array_stream_large_response() {
+    use futures::StreamExt;
+
+    let mock_server = MockServer::start().await;
+    let items: Vec<serde_json::Value> = (0..10_000)
+        .map(|i| serde_json::json!({"id": i, "name": format!("item [{}]", i), "tags": ["a", "b"]}))
+        .collect();
+
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&items))
+        .mount(&mock_server)
+        .await;
+
+    let response = fetch(&format!("{}/items", mock_server.uri()), None)
+        .await
+        .unwrap();
+
+    let mut stream = Box::pin(response.json_array_stream::<serde_json::Value>());
+    let mut count = 0;
+    while let Some(item) = stream.next().await {
+        let item = item.unwrap();
+        assert_eq!(item["id"], count);
+        count += 1;
+    }
+    assert_eq!(count, 10_000);
+}
+
+#[cfg(feature = "compression")]
+#[tokio::test]
+async fn test_gzip_json_response_decoded() {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder
+        .write_all(br#"{"name": "fetchttp", "compressed": true}"#)
+        .unwrap();
+    let gzipped = encoder.finish().unwrap();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/data"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-encoding", "gzip")
+                .set_body_raw(gzipped, "application/json"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let response = fetch(&format!("{}/data", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert!(!response.headers().has("content-encoding").unwrap());
+    let json: serde_json::Value = response.json().await.unwrap();
+    assert_eq!(json["name"], "fetchttp");
+    assert_eq!(json["compressed"], true);
+}
+
+#[cfg(feature = "compression")]
+#[tokio::test]
+async fn test_accept_encoding_sent_by_default() {
+    use std::io::Write;
+
+    let body = "compressible ".repeat(100);
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(body.as_bytes()).unwrap();
+    let gzipped = encoder.finish().unwrap();
+    let encoded_len = gzipped.len();
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(wiremock::matchers::headers(
+            "accept-encoding",
+            vec!["gzip", "br", "deflate"],
+        ))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-encoding", "gzip")
+                .set_body_bytes(gzipped),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(header("accept-encoding", "identity"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("identity"))
+        .mount(&mock_server)
+        .await;
+
+    let response = fetch(&mock_server.uri(), None).await.unwrap();
+    assert!(!response.headers().has("content-length").unwrap());
+    assert_eq!(
+        response.transfer_framing(),
+        TransferFraming::ContentLength(encoded_len as u64)
+    );
+    assert_eq!(response.text().await.unwrap(), body);
+
+    // A caller-supplied Accept-Encoding is sent as is
+    let mut init = RequestInit::new();
+    init.headers = Some(Headers::from(&[("Accept-Encoding", "identity")]));
+    let response = fetch(&mock_server.uri(), Some(init)).await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "identity");
+}
+
+#[cfg(feature = "compression")]
+#[tokio::test]
+async fn test_mislabeled_gzip_body() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/mislabeled"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-encoding", "gzip")
+                .set_body_string("not actually gzip"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let url = format!("{}/mislabeled", mock_server.uri());
+
+    let strict = FetchClient::new();
+    assert!(matches!(
+        strict.fetch(&url, None).await,
+        Err(FetchError::Network(_))
+    ));
+
+    let lenient = FetchClient::builder()
+        .lenient_decompression(true)
+        .build()
+        .unwrap();
+    let response = lenient.fetch(&url, None).await.unwrap();
+    assert!(!response.headers().has("content-encoding").unwrap());
+    assert_eq!(response.text().await.unwrap(), "not actually gzip");
+}
+
+#[tokio::test]
+async fn test_retry_replays_buffered_post_body() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/flaky"))
+        .respond_with(ResponseTemplate::new(503))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&mock_server)
+        .await;
+
+    Mock::given(method("POST"))
+        .and(path("/flaky"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("done"))
+        .mount(&mock_server)
+        .await;
+
+    let client = FetchClient::builder()
+        .retry_policy(Some(RetryPolicy::new(3)))
+        .build()
+        .unwrap();
+
+    let mut init = RequestInit::new();
+    init.method = Some("POST".to_string());
+    init.body = Some(ReadableStream::from_json(&serde_json::json!({"order": 42})));
+
+    let response = client
+        .fetch(&format!("{}/flaky", mock_server.uri()), Some(init))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "done");
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 3);
+    for request in &requests {
+        assert_eq!(request.body, br#"{"order":42}"#);
+        assert_eq!(
+            request.headers.get("content-type").unwrap(),
+            "application/json"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_retry_backoff_and_retry_after() {
+    let (base, connections) = raw_server(|index, _| match index {
+        0 => b"HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+            .to_vec(),
+        1 => b"HTTP/1.1 429 Too Many Requests\r\nretry-after: 1\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+            .to_vec(),
+        _ => b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok".to_vec(),
+    })
+    .await;
+
+    let client = FetchClient::builder()
+        .retry_policy(Some(
+            RetryPolicy::new(3)
+                .base_delay(std::time::Duration::from_millis(200))
+                .jitter(false),
+        ))
+        .build()
+        .unwrap();
+    let start = std::time::Instant::now();
+    let response = client.fetch(&base, None).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(connections.load(Ordering::SeqCst), 3);
+    // 200ms of backoff after the 503, then the second asked for by Retry-After
+    assert!(start.elapsed() >= std::time::Duration::from_millis(1200));
+}
+
+#[tokio::test]
+async fn test_fetch_ipv6_loopback() {
+    let (base, _) = raw_server_on("[::1]:0", |_, head| {
+        let host = head
+            .lines()
+            .find_map(|line| line.strip_prefix("host: "))
+            .unwrap_or("")
+            .to_string();
+        format!(
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+            host.len(),
+            host
+        )
+        .into_bytes()
+    })
+    .await;
+    assert!(base.starts_with("http://[::1]:"));
+
+    let response = fetch(&format!("{}/v6", base), None).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.url(), format!("{}/v6", base));
+
+    // The Host header keeps the brackets and the port
+    let host = response.text().await.unwrap();
+    assert_eq!(host, base.trim_start_matches("http://"));
+}
+
+#[tokio::test]
+async fn test_if_range_request_headers() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/download"))
+        .and(header("range", "bytes=100-"))
+        .and(header("if-range", "\"etag-1\""))
+        .respond_with(ResponseTemplate::new(206).set_body_string("rest"))
+        .mount(&mock_server)
+        .await;
+
+    let mut headers = Headers::new();
+    headers.set("Range", "bytes=100-").unwrap();
+    let mut init = RequestInit::new();
+    init.headers = Some(headers);
+    init.if_range("\"etag-1\"").unwrap();
+
+    let response = fetch(&format!("{}/download", mock_server.uri()), Some(init))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 206);
+    assert_eq!(response.text().await.unwrap(), "rest");
+}
+
+#[tokio::test]
+async fn test_extension_methods_reach_server_unaltered() {
+    let mock_server = MockServer::start().await;
+
+    for name in ["PURGE", "LINK", "MKCOL", "purge"] {
+        // wiremock's `method` matcher uppercases its argument, so match exactly
+        Mock::given(move |request: &wiremock::Request| request.method.as_str() == name)
+            .and(path("/resource"))
+            .respond_with(ResponseTemplate::new(204).insert_header("x-method", name))
+            .mount(&mock_server)
+            .await;
+    }
+
+    for name in ["PURGE", "LINK", "MKCOL", "purge"] {
+        let mut init = RequestInit::new();
+        init.method = Some(name.to_string());
+        if name == "MKCOL" {
+            init.body = Some(ReadableStream::from_text("<mkcol/>"));
+        }
+
+        let response = fetch(&format!("{}/resource", mock_server.uri()), Some(init))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 204, "method {}", name);
+        assert_eq!(response.headers().get("x-method").unwrap().unwrap(), name);
+    }
+
+    let received: Vec<String> = mock_server
+        .received_requests()
+        .await
+        .unwrap()
+        .iter()
+        .map(|request| request.method.to_string())
+        .collect();
+    assert_eq!(received, vec!["PURGE", "LINK", "MKCOL", "purge"]);
+}
+
+#[tokio::test]
+async fn test_per_request_max_redirects() {
+    let mock_server = MockServer::start().await;
+
+    for (from, to) in [("/r1", "/r2"), ("/r2", "/r3"), ("/r3", "/done")] {
+        Mock::given(method("GET"))
+            .and(path(from))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", to))
+            .mount(&mock_server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/done"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("done"))
+        .mount(&mock_server)
+        .await;
+
+    let url = format!("{}/r1", mock_server.uri());
+    let with_limit = |limit: usize| {
+        let mut init = RequestInit::new();
+        init.max_redirects = Some(limit);
+        Some(init)
+    };
+
+    // 0 rejects any redirect, 1 and 2 are below the chain length of 3
+    for limit in [0, 1, 2] {
+        match fetch_traced(&url, with_limit(limit)).await {
+            Err(FetchError::Network(e)) => {
+                assert!(e.to_string().contains("redirect"), "limit {}", limit)
+            }
+            other => panic!("limit {} should fail, got ok={}", limit, other.is_ok()),
+        }
+    }
+
+    let (response, chain) = fetch_traced(&url, with_limit(3)).await.unwrap();
+    assert_eq!(chain.len(), 4);
+    assert_eq!(response.text().await.unwrap(), "done");
+
+    // A limit of 0 does not affect requests that are not redirected
+    let done = format!("{}/done", mock_server.uri());
+    let (response, _) = fetch_traced(&done, with_limit(0)).await.unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_fetch_redirect_loop_hits_limit() {
+    let mock_server = MockServer::start().await;
+
+    for (from, to) in [("/a", "/b"), ("/b", "/a")] {
+        Mock::given(method("GET"))
+            .and(path(from))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", to))
+            .mount(&mock_server)
+            .await;
+    }
+    let url = format!("{}/a", mock_server.uri());
+
+    // The default limit of 20 stops the loop: the initial request plus 20 hops
+    match fetch(&url, None).await {
+        Err(FetchError::Network(e)) => {
+            assert_eq!(
+                e.to_string(),
+                "NetworkError: Maximum redirect count exceeded"
+            )
+        }
+        other => panic!("redirect loop should fail, got ok={}", other.is_ok()),
+    }
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 21);
+
+    // A per-request limit stops it sooner
+    mock_server.reset().await;
+    for (from, to) in [("/a", "/b"), ("/b", "/a")] {
+        Mock::given(method("GET"))
+            .and(path(from))
+            .respond_with(ResponseTemplate::new(302).insert_header("location", to))
+            .mount(&mock_server)
+            .await;
+    }
+    let mut init = RequestInit::new();
+    init.max_redirects = Some(3);
+    let result = fetch(&url, Some(init)).await;
+    assert!(matches!(result, Err(FetchError::Network(_))));
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests.len(), 4);
+}
+
+#[tokio::test]
+async fn test_connection_reset_retried_once_for_idempotent_requests() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    let connections = Arc::new(AtomicUsize::new(0));
+    {
+        let connections = connections.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let index = connections.fetch_add(1, Ordering::SeqCst);
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 4096];
+                    let _ = socket.read(&mut buf).await;
+                    if index.is_multiple_of(2) {
+                        // Reset the connection without sending anything
+                        socket.set_zero_linger().unwrap();
+                        return;
+                    }
+                    let _ = socket
+                        .write_all(
+                            b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\nconnection: close\r\n\r\nok",
+                        )
+                        .await;
+                });
+            }
+        });
+    }
+
+    // GET is resent on a fresh connection after the reset
+    let response = fetch(&base, None).await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "ok");
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+
+    // POST is not idempotent, so the reset is reported
+    let mut init = RequestInit::new();
+    init.method = Some("POST".to_string());
+    init.body = Some(ReadableStream::from_text("data"));
+    let result = fetch(&base, Some(init)).await;
+    assert!(matches!(result, Err(FetchError::Network(_))));
+    assert_eq!(connections.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_per_request_timeout() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/delay"))
+        .respond_with(ResponseTemplate::new(200).set_delay(std::time::Duration::from_secs(5)))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/fast"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let with_timeout = |ms: u64| {
+        let mut init = RequestInit::new();
+        init.timeout = Some(std::time::Duration::from_millis(ms));
+        Some(init)
+    };
+
+    let start = std::time::Instant::now();
+    match fetch(&format!("{}/delay", mock_server.uri()), with_timeout(10)).await {
+        Err(FetchError::Network(e)) => {
+            assert_eq!(e.to_string(), "NetworkError: Request timed out")
+        }
+        other => panic!("expected timeout, got ok={}", other.is_ok()),
+    }
+    assert!(start.elapsed() < std::time::Duration::from_secs(2));
+
+    let response = fetch(&format!("{}/fast", mock_server.uri()), with_timeout(5000))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    // The timeout also covers reading a body that stalls after the headers
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = socket.read(&mut buf).await;
+        let _ = socket
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 10\r\n\r\npart")
+            .await;
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    });
+    let result = fetch(&base, with_timeout(100)).await;
+    assert!(matches!(result, Err(FetchError::Network(_))));
+}
+
+#[tokio::test]
+async fn test_fetch_until_deadline() {
+    let (base, connections) =
+        raw_server(|_, _| b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok".to_vec()).await;
+    let now = std::time::Instant::now();
+
+    // A deadline in the past fails without connecting
+    let past = now - std::time::Duration::from_millis(1);
+    match fetch_until(&base, None, past).await {
+        Err(FetchError::Network(e)) => {
+            assert_eq!(e.to_string(), "NetworkError: Request timed out")
+        }
+        other => panic!("expected timeout, got ok={}", other.is_ok()),
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 0);
+
+    let future = now + std::time::Duration::from_secs(5);
+    let response = fetch_until(&base, None, future).await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "ok");
+
+    let mock_server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/slow"))
+        .respond_with(ResponseTemplate::new(200).set_delay(std::time::Duration::from_secs(5)))
+        .mount(&mock_server)
+        .await;
+    let soon = std::time::Instant::now() + std::time::Duration::from_millis(100);
+    let result = FetchClient::new()
+        .fetch_until(&format!("{}/slow", mock_server.uri()), None, soon)
+        .await;
+    assert!(matches!(result, Err(FetchError::Network(_))));
+}
+
+#[tokio::test]
+async fn test_write_timeout_stalled_upload() {
+    // Accept the connection and read the request head, then stop reading
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = socket.read(&mut buf).await;
+        tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+    });
+
+    let client = FetchClient::builder()
+        .write_timeout(Some(std::time::Duration::from_millis(200)))
+        .build()
+        .unwrap();
+
+    // Large enough to fill the socket buffers on both ends
+    let mut init = RequestInit::new();
+    init.method = Some("POST".to_string());
+    init.body = Some(ReadableStream::from_bytes(Bytes::from(vec![
+        b'x';
+        64 * 1024
+            * 1024
+    ])));
+
+    let start = std::time::Instant::now();
+    match client.fetch(&base, Some(init)).await {
+        Err(FetchError::Network(e)) => {
+            assert_eq!(e.to_string(), "NetworkError: Request body write timed out")
+        }
+        other => panic!("expected write timeout, got ok={}", other.is_ok()),
+    }
+    assert!(start.elapsed() < std::time::Duration::from_secs(10));
+}
+
+#[tokio::test]
+async fn test_write_timeout_completed_upload() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("PUT"))
+        .and(path("/upload"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&mock_server)
+        .await;
+
+    let client = FetchClient::builder()
+        .write_timeout(Some(std::time::Duration::from_millis(200)))
+        .build()
+        .unwrap();
+
+    let mut init = RequestInit::new();
+    init.method = Some("PUT".to_string());
+    init.body = Some(ReadableStream::from_bytes(Bytes::from(vec![b'x'; 300_000])));
+    let response = client
+        .fetch(&format!("{}/upload", mock_server.uri()), Some(init))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 204);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests[0].body.len(), 300_000);
+}
+
+#[tokio::test]
+async fn test_stream_body_above_threshold() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/large"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let client = FetchClient::builder()
+        .stream_body_above(Some(1024))
+        .build()
+        .unwrap();
+
+    let payload: Vec<u8> = (0..1_000_000u32).map(|i| (i % 251) as u8).collect();
+    let mut init = RequestInit::new();
+    init.method = Some("POST".to_string());
+    init.body = Some(ReadableStream::from_bytes(Bytes::from(payload.clone())));
+    let response = client
+        .fetch(&format!("{}/large", mock_server.uri()), Some(init))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let requests = mock_server.received_requests().await.unwrap();
+    assert_eq!(requests[0].body, payload);
+    assert_eq!(
+        requests[0].headers.get("content-length").unwrap(),
+        "1000000"
+    );
+    assert!(requests[0].headers.get("transfer-encoding").is_none());
+}
+
+#[tokio::test]
+async fn test_transfer_framing() {
+    let (base, _) = raw_server(|_, head| {
+        if head.starts_with("GET /chunked ") {
+            b"HTTP/1.1 200 OK\r\ntransfer-encoding: chunked\r\n\r\n5\r\nhello\r\n0\r\n\r\n".to_vec()
+        } else {
+            b"HTTP/1.1 200 OK\r\ncontent-length: 5\r\n\r\nhello".to_vec()
+        }
+    })
+    .await;
+
+    let response = fetch(&format!("{}/length", base), None).await.unwrap();
+    assert_eq!(
+        response.transfer_framing(),
+        TransferFraming::ContentLength(5)
+    );
+    assert_eq!(response.text().await.unwrap(), "hello");
+
+    let response = fetch(&format!("{}/chunked", base), None).await.unwrap();
+    assert_eq!(response.transfer_framing(), TransferFraming::Chunked);
+    assert_eq!(response.text().await.unwrap(), "hello");
+}
+
+#[tokio::test]
+async fn test_response_exposes_connector_extensions() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let response = fetch(&mock_server.uri(), None).await.unwrap();
+    assert_eq!(response.version(), http::Version::HTTP_11);
+
+    // The HTTP connector attaches the socket addresses of the connection
+    let info = response
+        .extensions()
+        .get::<hyper_util::client::legacy::connect::HttpInfo>()
+        .expect("connector should attach HttpInfo");
+    assert_eq!(info.remote_addr(), *mock_server.address());
+}
+
+#[tokio::test]
+async fn test_abort_in_flight_request() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/delay"))
+        .respond_with(ResponseTemplate::new(200).set_delay(std::time::Duration::from_secs(5)))
+        .mount(&mock_server)
+        .await;
+
+    // Abort while waiting for the response headers
+    let controller = AbortController::new();
+    let mut init = RequestInit::new();
+    init.signal = Some(controller.signal().clone());
+    tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        controller.abort();
+    });
+    let start = std::time::Instant::now();
+    let result = fetch(&format!("{}/delay", mock_server.uri()), Some(init)).await;
+    assert!(matches!(result, Err(FetchError::Abort(_))));
+    assert!(start.elapsed() < std::time::Duration::from_secs(2));
+
+    // Abort while the body is still downloading
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut buf = [0u8; 4096];
+        let _ = socket.read(&mut buf).await;
+        let _ = socket
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 10\r\n\r\npart")
+            .await;
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    });
+    let mut init = RequestInit::new();
+    init.signal = Some(AbortSignal::timeout(std::time::Duration::from_millis(100)));
+    let start = std::time::Instant::now();
+    let result = fetch(&base, Some(init)).await;
+    assert!(matches!(result, Err(FetchError::Abort(_))));
+    assert!(start.elapsed() < std::time::Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_response_not_pushed() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/plain"))
+        .respond_with(ResponseTemplate::new(200))
+        .mount(&mock_server)
+        .await;
+
+    let response = fetch(&format!("{}/plain", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert!(!response.was_pushed());
+}
+
+#[tokio::test]
+async fn test_fetch_json_parses_body() {
+    #[derive(serde::Deserialize)]
+    struct User {
+        id: u32,
+        name: String,
+    }
+
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/user"))
+        .respond_with(ResponseTemplate::new(200).set_body_raw(
+            r#"{"id": 1, "name": "Ada"}"#,
+            "application/json; charset=utf-8",
+        ))
+        .mount(&mock_server)
+        .await;
+
+    let user: User = fetch_json(&format!("{}/user", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert_eq!(user.id, 1);
+    assert_eq!(user.name, "Ada");
+}
+
+#[tokio::test]
+async fn test_fetch_json_non_2xx_status_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/missing"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "error": "not found"
+        })))
+        .mount(&mock_server)
+        .await;
+
+    let result =
+        fetch_json::<serde_json::Value>(&format!("{}/missing", mock_server.uri()), None).await;
+    match result {
+        Err(FetchJsonError::Status(e)) => {
+            assert_eq!(e.status(), 404);
+            assert_eq!(e.status_text(), "Not Found");
+        }
+        other => panic!("expected a status error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[tokio::test]
+async fn test_fetch_all_bounded_concurrency() {
+    use futures::StreamExt;
+
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+    {
+        let (in_flight, peak) = (in_flight.clone(), peak.clone());
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let (in_flight, peak) = (in_flight.clone(), peak.clone());
+                tokio::spawn(async move {
+                    let mut buf = [0u8; 1024];
+                    let n = socket.read(&mut buf).await.unwrap();
+                    let path = String::from_utf8_lossy(&buf[..n])
+                        .split_whitespace()
+                        .nth(1)
+                        .unwrap()
+                        .to_string();
+                    let now = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    peak.fetch_max(now, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        path.len(),
+                        path
+                    );
+                    socket.write_all(response.as_bytes()).await.unwrap();
+                });
+            }
+        });
+    }
+
+    let urls: Vec<String> = (0..6).map(|i| format!("{}/item/{}", base, i)).collect();
+    let results: Vec<(String, Result<Response>)> = fetch_all(urls.clone(), 2).collect().await;
+
+    assert_eq!(results.len(), urls.len());
+    let mut seen = Vec::new();
+    for (url, result) in results {
+        let body = result.unwrap().text().await.unwrap();
+        assert!(url.ends_with(&body));
+        seen.push(url);
+    }
+    seen.sort();
+    assert_eq!(seen, urls);
+    assert!(peak.load(Ordering::SeqCst) <= 2);
+}
+
+#[tokio::test]
+async fn test_send_returns_status_and_reuses_connection() {
+    let (base, connections) = raw_server(|_, head| {
+        if head.starts_with("GET /missing ") {
+            b"HTTP/1.1 404 Not Found\r\ncontent-length: 9\r\n\r\nnot found".to_vec()
+        } else {
+            b"HTTP/1.1 202 Accepted\r\ncontent-length: 8\r\n\r\naccepted".to_vec()
+        }
+    })
+    .await;
+
+    assert_eq!(send(&format!("{}/hook", base), None).await.unwrap(), 202);
+    assert_eq!(send(&format!("{}/missing", base), None).await.unwrap(), 404);
+    assert_eq!(send(&format!("{}/hook", base), None).await.unwrap(), 202);
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_no_pool_opens_fresh_connections() {
+    let (base, connections) = raw_server(|_, head| {
+        let close = head.to_ascii_lowercase().contains("connection: close");
+        let body = if close { "close" } else { "keep" };
+        format!(
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .into_bytes()
+    })
+    .await;
+
+    let client = FetchClient::builder().no_pool().build().unwrap();
+    for _ in 0..2 {
+        let response = client.fetch(&format!("{}/", base), None).await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "close");
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+
+    // A pooled client reuses the connection for the same requests
+    let (base, connections) =
+        raw_server(|_, _| b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok".to_vec()).await;
+    let client = FetchClient::new();
+    for _ in 0..2 {
+        let response = client.fetch(&format!("{}/", base), None).await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "ok");
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_one_request_per_connection_sends_connection_close() {
+    let (base, connections) = raw_server(|_, head| {
+        // Answer like a compliant server, closing when the client asks it to
+        if head
+            .to_ascii_lowercase()
+            .contains("\r\nconnection: close\r\n")
+        {
+            b"HTTP/1.1 200 OK\r\ncontent-length: 5\r\nconnection: close\r\n\r\nclose".to_vec()
+        } else {
+            b"HTTP/1.1 200 OK\r\ncontent-length: 4\r\n\r\nkeep".to_vec()
+        }
+    })
+    .await;
+
+    let client = FetchClient::builder()
+        .one_request_per_connection()
+        .build()
+        .unwrap();
+    for _ in 0..2 {
+        let response = client.fetch(&format!("{}/", base), None).await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "close");
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+}
+
+#[cfg(feature = "compression")]
+#[tokio::test]
+async fn test_transfer_encoding_gzip_body() {
+    use std::io::Write;
+
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(b"gzip as a transfer coding").unwrap();
+    let gzipped = encoder.finish().unwrap();
+
+    let (base, _) = raw_server(move |_, _| {
+        let mut response = format!(
+            "HTTP/1.1 200 OK\r\ntransfer-encoding: gzip, chunked\r\n\r\n{:x}\r\n",
+            gzipped.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(&gzipped);
+        response.extend_from_slice(b"\r\n0\r\n\r\n");
+        response
+    })
+    .await;
+
+    let response = fetch(&format!("{}/", base), None).await.unwrap();
+    assert_eq!(
+        response
+            .headers()
+            .get("transfer-encoding")
+            .unwrap()
+            .unwrap(),
+        "chunked"
+    );
+    assert_eq!(response.text().await.unwrap(), "gzip as a transfer coding");
+}
+
+#[tokio::test]
+async fn test_early_hints_callback() {
+    let (base, _) = raw_server(|_, _| {
+        b"HTTP/1.1 103 Early Hints\r\nlink: </style.css>; rel=preload; as=style\r\n\r\n\
+          HTTP/1.1 200 OK\r\ncontent-length: 4\r\n\r\ndone"
+            .to_vec()
+    })
+    .await;
+
+    let hints = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let mut init = RequestInit::new();
+    init.on_early_hints = Some({
+        let hints = hints.clone();
+        Arc::new(move |headers: &Headers| {
+            hints
+                .lock()
+                .unwrap()
+                .push(headers.get("link").unwrap().unwrap());
+        })
+    });
+
+    let response = fetch(&format!("{}/page", base), Some(init)).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "done");
+    assert_eq!(
+        *hints.lock().unwrap(),
+        vec!["</style.css>; rel=preload; as=style".to_string()]
+    );
+}
+
+#[tokio::test]
+async fn test_verify_content_md5() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/good"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-md5", "XrY7u+Ae7tCTyyK7j1rNww==")
+                .set_body_string("hello world"),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/bad"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("content-md5", "XrY7u+Ae7tCTyyK7j1rNww==")
+                .set_body_string("tampered"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let client = FetchClient::builder()
+        .verify_content_md5(true)
+        .build()
+        .unwrap();
+
+    let response = client
+        .fetch(&format!("{}/good", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "hello world");
+
+    let bad = format!("{}/bad", mock_server.uri());
+    assert!(matches!(
+        client.fetch(&bad, None).await,
+        Err(FetchError::Network(_))
+    ));
+
+    // Verification is opt-in
+    let response = FetchClient::new().fetch(&bad, None).await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "tampered");
+}
+
+#[tokio::test]
+async fn test_fetch_reports_final_url_after_redirects() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/old"))
+        .respond_with(ResponseTemplate::new(301).insert_header("location", "/interim"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/interim"))
+        .respond_with(ResponseTemplate::new(308).insert_header("location", "/new"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/new"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("moved"))
+        .mount(&mock_server)
+        .await;
+
+    let response = fetch(&format!("{}/old", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert_eq!(response.url(), format!("{}/new", mock_server.uri()));
+    assert!(response.redirected());
+
+    // Without a hop the URL is the request URL and the flag stays false
+    let direct = format!("{}/new", mock_server.uri());
+    let response = fetch(&direct, None).await.unwrap();
+    assert_eq!(response.url(), direct);
+    assert!(!response.redirected());
+}
+
+#[tokio::test]
+async fn test_redirect_mode_error() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/redirect"))
+        .respond_with(ResponseTemplate::new(302).insert_header("location", "/target"))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/target"))
+        .respond_with(ResponseTemplate::new(200))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let error_mode = || {
+        let mut init = RequestInit::new();
+        init.redirect = Some(RequestRedirect::Error);
+        Some(init)
+    };
+
+    let result = fetch(&format!("{}/redirect", mock_server.uri()), error_mode()).await;
+    assert!(matches!(result, Err(FetchError::Network(_))));
+
+    // Non-redirect responses are unaffected
+    let response = fetch(&format!("{}/target", mock_server.uri()), error_mode())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_conflicting_content_length_rejected() {
+    let (base, _) = raw_server(|_, _| {
+        b"HTTP/1.1 200 OK\r\ncontent-length: 5\r\ncontent-length: 6\r\n\r\nhello!".to_vec()
+    })
+    .await;
+    // Differing lengths are refused by the HTTP parser
+    match fetch(&format!("{}/", base), None).await {
+        Err(FetchError::Network(e)) => {
+            assert!(e.message().starts_with("Malformed HTTP response"), "{}", e)
+        }
+        other => panic!("expected a network error, got {:?}", other.map(|_| ())),
+    }
+
+    // Equal duplicates are collapsed
+    let (base, _) = raw_server(|_, _| {
+        b"HTTP/1.1 200 OK\r\ncontent-length: 5\r\ncontent-length: 5\r\n\r\nhello".to_vec()
+    })
+    .await;
+    let response = fetch(&format!("{}/", base), None).await.unwrap();
+    assert_eq!(
+        response.headers().get("content-length").unwrap().unwrap(),
+        "5"
+    );
+    assert_eq!(response.text().await.unwrap(), "hello");
+}
+
+#[tokio::test]
+async fn test_ordered_headers_sent_in_insertion_order() {
+    let (base, _) = raw_server(|_, head| {
+        let body = head.to_string();
+        format!(
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .into_bytes()
+    })
+    .await;
+
+    let names = [
+        "x-zeta",
+        "user-agent",
+        "accept",
+        "x-alpha",
+        "accept-language",
+    ];
+    let mut headers = Headers::ordered();
+    for name in names {
+        headers.set(name, "v").unwrap();
+    }
+    let mut init = RequestInit::new();
+    init.headers = Some(headers);
+
+    let response = fetch(&base, Some(init)).await.unwrap();
+    let head = response.text().await.unwrap();
+    let sent: Vec<_> = head
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, _)| name.to_ascii_lowercase())
+        .filter(|name| names.contains(&name.as_str()))
+        .collect();
+    assert_eq!(sent, names);
+}
+
+#[tokio::test]
+async fn test_repeated_request_headers_sent_in_full() {
+    let (base, _) = raw_server(|_, head| {
+        let body = head.to_lowercase();
+        format!(
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .into_bytes()
+    })
+    .await;
+
+    let mut headers = Headers::new();
+    headers.append("Accept", "application/json").unwrap();
+    headers.append("Accept", "text/plain").unwrap();
+    let mut init = RequestInit::new();
+    init.headers = Some(headers);
+
+    let head = fetch(&base, Some(init))
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    assert!(head.contains("\r\naccept: application/json\r\n"));
+    assert!(head.contains("\r\naccept: text/plain\r\n"));
+}
+
+#[tokio::test]
+async fn test_appended_cookie_values_all_sent() {
+    let (base, _) = raw_server(|_, head| {
+        let body = head.to_lowercase();
+        format!(
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .into_bytes()
+    })
+    .await;
+
+    let mut headers = Headers::new();
+    headers.append("Cookie", "session=abc").unwrap();
+    headers.append("Cookie", "theme=dark").unwrap();
+    let mut init = RequestInit::new();
+    init.headers = Some(headers);
+
+    let head = fetch(&base, Some(init))
+        .await
+        .unwrap()
+        .text()
+        .await
+        .unwrap();
+    let cookies: Vec<_> = head
+        .lines()
+        .filter_map(|line| line.strip_prefix("cookie: "))
+        .collect();
+    assert_eq!(cookies, ["session=abc", "theme=dark"]);
+}
+
+#[tokio::test]
+async fn test_explicit_empty_body_sends_content_length() {
+    let (base, _) = raw_server(|_, head| {
+        let body = head.to_lowercase();
+        format!(
+            "HTTP/1.1 200 OK\r\ncontent-length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        )
+        .into_bytes()
+    })
+    .await;
+    let head = |body: Option<ReadableStream>| {
+        let mut init = RequestInit::new();
+        init.method = Some("POST".to_string());
+        init.body = body;
+        let base = base.clone();
+        async move {
+            fetch(&base, Some(init))
+                .await
+                .unwrap()
+                .text()
+                .await
+                .unwrap()
+        }
+    };
+
+    let empty = head(Some(ReadableStream::empty())).await;
+    assert!(empty.contains("\r\ncontent-length: 0\r\n"));
+
+    let bodyless = head(None).await;
+    assert!(!bodyless.contains("content-length"));
+    assert!(!bodyless.contains("transfer-encoding"));
+}
+
+#[tokio::test]
+async fn test_host_allowlist() {
+    let (base, connections) =
+        raw_server(|_, _| b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok".to_vec()).await;
+    let port = base.rsplit(':').next().unwrap().to_string();
+
+    let client = FetchClient::builder()
+        .host_allowlist(Some(vec!["127.0.0.1".to_string()]))
+        .build()
+        .unwrap();
+    let response = client.fetch(&base, None).await.unwrap();
+    assert_eq!(response.text().await.unwrap(), "ok");
+
+    let result = client
+        .fetch(&format!("http://localhost:{}", port), None)
+        .await;
+    match result {
+        Err(FetchError::Type(e)) => assert!(e.message().contains("localhost")),
+        other => panic!("expected a type error, got ok={}", other.is_ok()),
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn test_block_private_ips() {
+    let (base, connections) =
+        raw_server(|_, _| b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok".to_vec()).await;
+    let port = base.rsplit(':').next().unwrap().to_string();
+
+    let client = FetchClient::builder()
+        .block_private_ips(true)
+        .build()
+        .unwrap();
+
+    // Both a literal loopback address and a name resolving to one are refused
+    let literal = client.fetch(&base, None).await;
+    assert!(matches!(literal, Err(FetchError::Type(_))));
+    let resolved = client
+        .fetch(&format!("http://localhost:{}", port), None)
+        .await;
+    match resolved {
+        Err(FetchError::Type(e)) => assert!(e.message().contains("private address")),
+        other => panic!("expected a type error, got ok={}", other.is_ok()),
+    }
+    assert_eq!(connections.load(Ordering::SeqCst), 0);
+
+    let response = FetchClient::new().fetch(&base, None).await.unwrap();
+    assert_eq!(response.status(), 200);
+}
+
+#[tokio::test]
+async fn test_cross_origin_redirect_drops_credentials() {
+    let origin = MockServer::start().await;
+    let other = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/start"))
+        .respond_with(
+            ResponseTemplate::new(302)
+                .insert_header("location", format!("{}/landing", other.uri()).as_str()),
+        )
+        .mount(&origin)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/landing"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("landed"))
+        .mount(&other)
+        .await;
+
+    let mut headers = Headers::new();
+    headers.set("Cookie", "session=abc123").unwrap();
+    headers.set("Authorization", "Bearer token").unwrap();
+    let mut init = RequestInit::new();
+    init.headers = Some(headers);
+    let response = fetch(&format!("{}/start", origin.uri()), Some(init))
+        .await
+        .unwrap();
+    assert_eq!(response.text().await.unwrap(), "landed");
+
+    let sent = origin.received_requests().await.unwrap();
+    assert!(sent[0].headers.contains_key("cookie"));
+    let received = other.received_requests().await.unwrap();
+    assert_eq!(received.len(), 1);
+    assert!(!received[0].headers.contains_key("cookie"));
+    assert!(!received[0].headers.contains_key("authorization"));
+}
+
+#[tokio::test]
+async fn test_cookie_store() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("POST"))
+        .and(path("/login"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .insert_header("set-cookie", "session=abc123; Path=/; HttpOnly"),
+        )
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/protected"))
+        .and(header("cookie", "session=abc123"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("welcome"))
+        .mount(&mock_server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/protected"))
+        .respond_with(ResponseTemplate::new(401))
+        .mount(&mock_server)
+        .await;
+
+    let client = FetchClient::builder().cookie_store(true).build().unwrap();
+    let with_credentials = |method: &str| {
+        let mut init = RequestInit::new();
+        init.method = Some(method.to_string());
+        init.credentials = Some(RequestCredentials::Include);
+        init
+    };
+
+    let response = client
+        .fetch(
+            &format!("{}/protected", mock_server.uri()),
+            Some(with_credentials("GET")),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+
+    let response = client
+        .fetch(
+            &format!("{}/login", mock_server.uri()),
+            Some(with_credentials("POST")),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+
+    let response = client
+        .fetch(
+            &format!("{}/protected", mock_server.uri()),
+            Some(with_credentials("GET")),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "welcome");
+
+    // Requests not including credentials leave the store alone
+    let response = client
+        .fetch(&format!("{}/protected", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 401);
+}
+
+#[tokio::test]
+async fn test_repeated_response_headers_kept() {
+    let mock_server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/cookies"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .append_header(
+                    "set-cookie",
+                    "session=abc; Expires=Wed, 21 Oct 2026 07:28:00 GMT",
+                )
+                .append_header("set-cookie", "theme=dark"),
+        )
+        .mount(&mock_server)
+        .await;
+
+    let response = fetch(&format!("{}/cookies", mock_server.uri()), None)
+        .await
+        .unwrap();
+    assert_eq!(
+        response.headers().get_set_cookie(),
+        [
+            "session=abc; Expires=Wed, 21 Oct 2026 07:28:00 GMT",
+            "theme=dark"
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_misdirected_request_retried_on_new_connection() {
+    // The first connection cannot serve this authority; later ones can
+    let (base, connections) = raw_server(|index, _| {
+        if index == 0 {
+            b"HTTP/1.1 421 Misdirected Request\r\ncontent-length: 0\r\n\r\n".to_vec()
+        } else {
+            b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok".to_vec()
+        }
+    })
+    .await;
+    let client = FetchClient::new();
+    let response = client.fetch(&format!("{}/", base), None).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.text().await.unwrap(), "ok");
+    assert_eq!(connections.load(Ordering::SeqCst), 2);
+
+    // Non-idempotent requests are not resent
+    let (base, connections) = raw_server(|_, _| {
+        b"HTTP/1.1 421 Misdirected Request\r\ncontent-length: 0\r\n\r\n".to_vec()
+    })
+    .await;
+    let mut init = RequestInit::new();
+    init.method = Some("POST".to_string());
+    init.body = Some(ReadableStream::from_text("data"));
+    let response = client
+        .fetch(&format!("{}/", base), Some(init))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), 421);
+    assert_eq!(connections.load(Ordering::SeqCst), 1);
+}